@@ -17,7 +17,7 @@ fn main() {
 fn parse_spells(raw_spells: &str) -> Vec<Spell> {
     let mut spells: Vec<Spell> = vec![];
 
-    for line in raw_spells.lines().into_iter() {
+    for line in raw_spells.lines() {
         let parts: Vec<String> = line.split("\t").map(|part| part.to_string()).collect();
 
         if parts.len() < 8 {
@@ -1,10 +1,13 @@
-use serde::{Deserialize, Serialize};
+use serde::{de, Deserialize, Deserializer, Serialize};
 use std::collections::HashMap;
+use std::path::Path;
 
 pub const MUTATED_SPELLS_JSON: &str = "spells_mutated.json";
 pub const MUTATED_SPELLS_FILE: &str = "mutated spells.txt";
 pub const MUTATED_WORDS_FILE: &str = "mutated words.txt";
 pub const DIAGNOSTICS_FILE: &str = "diagnostics.txt";
+pub const SPOONERISMS_JSON: &str = "spoonerisms.json";
+pub const SPOONERISMS_FILE: &str = "spoonerisms.txt";
 
 #[derive(Serialize, Deserialize, Eq, PartialEq, Ord, PartialOrd, Debug)]
 pub struct Spell {
@@ -70,5 +73,388 @@ pub struct MutationConfig {
     pub output_dir: String,
     pub mutation_depth: usize,
     pub advanced_diagnostics: bool,
+    // otherwise spells without mutations are listed as "no mutations" in the text output and
+    // with a null in place of their mutations in the json
     pub omit_zero_mutation_spells: bool,
+    #[serde(default = "default_operators")]
+    pub operators: Vec<OperatorConfig>,
+    #[serde(default)]
+    pub show_edit_paths: bool,
+    #[serde(default = "default_spell_checkers")]
+    pub spell_checkers: Vec<SpellCheckerEntry>,
+    #[serde(default)]
+    pub blocklist: BlocklistConfig,
+    #[serde(default)]
+    pub phonetic: Option<PhoneticConfig>,
+    #[serde(default)]
+    pub homophones: HomophoneConfig,
+    #[serde(default)]
+    pub spoonerisms: Option<SpoonerismConfig>,
+    #[serde(default)]
+    pub keyboard: Option<KeyboardConfig>,
+}
+
+impl MutationConfig {
+    // paths in a config file are relative to that file rather than the working directory
+    pub fn resolve_paths(&mut self, base: &Path) {
+        let resolve = |path: &mut String| *path = base.join(&*path).to_string_lossy().into_owned();
+        resolve(&mut self.input_file);
+        resolve(&mut self.overrides_file);
+        resolve(&mut self.output_dir);
+        for entry in &mut self.spell_checkers {
+            entry.checker.paths_mut().into_iter().for_each(resolve);
+        }
+        self.blocklist.files.iter_mut().for_each(resolve);
+        if let Some(phonetic) = &mut self.phonetic {
+            resolve(&mut phonetic.dictionary);
+        }
+        resolve(&mut self.homophones.file);
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct BlocklistConfig {
+    // one word or phrase per line, the 12dicts '!' and '*' annotations mark neologisms and cross
+    // references rather than offensive words so they aren't read as blocklists
+    #[serde(default)]
+    pub files: Vec<String>,
+    // keep blocked mutations with their own check result instead of dropping them
+    #[serde(default)]
+    pub tag: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct PhoneticConfig {
+    // words the phonetic mutations can turn into
+    #[serde(default = "default_phonetic_dictionary")]
+    pub dictionary: String,
+    // how many sounds a word may differ by, each of them costs a mutation level
+    #[serde(default = "default_phonetic_distance")]
+    pub max_distance: usize,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct HomophoneConfig {
+    // one group of words that sound the same per line
+    #[serde(default = "default_homophone_file")]
+    pub file: String,
+    // swapping a word for its homophone doesn't cost a mutation level
+    #[serde(default)]
+    pub free: bool,
+}
+
+impl Default for HomophoneConfig {
+    fn default() -> Self {
+        Self {
+            file: default_homophone_file(),
+            free: false,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SpoonerismConfig {
+    // also swap the vowels following the leading consonants
+    #[serde(default)]
+    pub vowels: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct KeyboardConfig {
+    // rows of keys from the top, every row sits half a key further right than the one above it
+    #[serde(default = "default_keyboard_layout")]
+    pub layout: Vec<String>,
+    // only change or add letters whose key neighbours the one that was meant, and leave out the
+    // phonetic mutations and spoonerisms
+    #[serde(default)]
+    pub restrict: bool,
+}
+
+#[derive(Serialize, Deserialize, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum MutationOperator {
+    Add,
+    Change,
+    Remove,
+    Swap,
+    Homophone,
+    DropWord,
+    SwapWords,
+    DuplicateWord,
+}
+
+impl MutationOperator {
+    // operators that replace whole words rather than editing single letters
+    pub fn is_word_level(self) -> bool {
+        matches!(
+            self,
+            MutationOperator::Homophone
+                | MutationOperator::DropWord
+                | MutationOperator::SwapWords
+                | MutationOperator::DuplicateWord
+        )
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct OperatorConfig {
+    pub operator: MutationOperator,
+    #[serde(default)]
+    pub max_uses: Option<usize>,
+}
+
+fn default_operators() -> Vec<OperatorConfig> {
+    [MutationOperator::Add, MutationOperator::Change, MutationOperator::Remove]
+        .into_iter()
+        .map(|operator| OperatorConfig { operator, max_uses: None })
+        .collect()
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SpellCheckerEntry {
+    // names the output directory in place of the checker's name, so the same checker can run
+    // more than once with different options
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(flatten)]
+    pub checker: SpellCheckerConfig,
+}
+
+impl SpellCheckerEntry {
+    pub fn name(&self) -> &str {
+        self.label.as_deref().unwrap_or(self.checker.name())
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(tag = "name")]
+pub enum SpellCheckerConfig {
+    Lemma {
+        #[serde(default = "default_lemma_dictionary")]
+        dictionary: String,
+    },
+    Frequency {
+        #[serde(default = "default_frequency_dictionary")]
+        dictionary: String,
+        max_tier: Option<u8>,
+    },
+    Legacy {
+        #[serde(default = "default_legacy_affixes")]
+        affixes: String,
+        #[serde(default = "default_legacy_dictionary")]
+        dictionary: String,
+    },
+    PartOfSpeech {
+        #[serde(default = "default_part_of_speech_dictionary")]
+        dictionary: String,
+    },
+    Phrase {
+        #[serde(default = "default_phrase_dictionary")]
+        dictionary: String,
+        #[serde(default = "default_wrapped_checker")]
+        checker: Box<SpellCheckerConfig>,
+    },
+    ProperNoun {
+        #[serde(default = "default_proper_noun_dictionary")]
+        dictionary: String,
+        #[serde(default = "default_signatures")]
+        signatures: Vec<String>,
+        #[serde(default = "default_wrapped_checker")]
+        checker: Box<SpellCheckerConfig>,
+    },
+    Variant {
+        #[serde(default = "default_variant_dictionary")]
+        dictionary: String,
+        #[serde(default)]
+        keep_variants: bool,
+        #[serde(default = "default_wrapped_checker")]
+        checker: Box<SpellCheckerConfig>,
+    },
+    Composite {
+        #[serde(default)]
+        accept: CompositeAccept,
+        #[serde(default)]
+        result: CompositeResult,
+        checkers: Vec<CompositeMember>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Copy, Clone, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum CompositeAccept {
+    #[default]
+    All,
+    Any,
+}
+
+#[derive(Serialize, Deserialize, Copy, Clone, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum CompositeResult {
+    Best,
+    #[default]
+    Worst,
+    First,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct CompositeMember {
+    pub checker: SpellCheckerConfig,
+    // codes of this checker that count as rejections
+    #[serde(default)]
+    pub reject: Vec<u8>,
+    #[serde(default, deserialize_with = "deserialize_remap")]
+    pub remap: HashMap<u8, u8>,
+}
+
+// tagged enums buffer their fields, which turns the json keys into strings that serde won't read
+// as numbers anymore
+fn deserialize_remap<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<HashMap<u8, u8>, D::Error> {
+    HashMap::<String, u8>::deserialize(deserializer)?
+        .into_iter()
+        .map(|(code, remapped)| Ok((code.parse().map_err(de::Error::custom)?, remapped)))
+        .collect()
+}
+
+impl SpellCheckerConfig {
+    pub fn name(&self) -> &str {
+        match self {
+            SpellCheckerConfig::Lemma { .. } => "Lemma",
+            SpellCheckerConfig::Frequency { .. } => "Frequency",
+            SpellCheckerConfig::Legacy { .. } => "Legacy",
+            SpellCheckerConfig::PartOfSpeech { .. } => "PartOfSpeech",
+            SpellCheckerConfig::Phrase { .. } => "Phrase",
+            SpellCheckerConfig::ProperNoun { .. } => "ProperNoun",
+            SpellCheckerConfig::Variant { .. } => "Variant",
+            SpellCheckerConfig::Composite { .. } => "Composite",
+        }
+    }
+
+    fn paths_mut(&mut self) -> Vec<&mut String> {
+        match self {
+            SpellCheckerConfig::Lemma { dictionary } => vec![dictionary],
+            SpellCheckerConfig::Frequency { dictionary, .. } => vec![dictionary],
+            SpellCheckerConfig::Legacy {
+                affixes,
+                dictionary,
+            } => vec![affixes, dictionary],
+            SpellCheckerConfig::PartOfSpeech { dictionary } => vec![dictionary],
+            SpellCheckerConfig::Phrase {
+                dictionary,
+                checker,
+            } => {
+                let mut paths = checker.paths_mut();
+                paths.push(dictionary);
+                paths
+            }
+            SpellCheckerConfig::ProperNoun {
+                dictionary,
+                signatures,
+                checker,
+            } => {
+                let mut paths = checker.paths_mut();
+                paths.push(dictionary);
+                paths.extend(signatures);
+                paths
+            }
+            SpellCheckerConfig::Variant {
+                dictionary,
+                checker,
+                ..
+            } => {
+                let mut paths = checker.paths_mut();
+                paths.push(dictionary);
+                paths
+            }
+            SpellCheckerConfig::Composite { checkers, .. } => checkers
+                .iter_mut()
+                .flat_map(|member| member.checker.paths_mut())
+                .collect(),
+        }
+    }
+}
+
+fn default_spell_checkers() -> Vec<SpellCheckerEntry> {
+    [
+        SpellCheckerConfig::Lemma {
+            dictionary: default_lemma_dictionary(),
+        },
+        SpellCheckerConfig::Frequency {
+            dictionary: default_frequency_dictionary(),
+            max_tier: None,
+        },
+    ]
+    .into_iter()
+    .map(|checker| SpellCheckerEntry { label: None, checker })
+    .collect()
+}
+
+fn default_lemma_dictionary() -> String {
+    "./dicts/12dicts-6.0.2/Lemmatized/2+2+3lem.txt".to_string()
+}
+
+fn default_frequency_dictionary() -> String {
+    "./dicts/12dicts-6.0.2/Lemmatized/2+2+3frq.txt".to_string()
+}
+
+fn default_legacy_affixes() -> String {
+    "./dicts/lang_en_US.aff".to_string()
+}
+
+fn default_legacy_dictionary() -> String {
+    "./dicts/lang_en_US_DICT.dic".to_string()
+}
+
+fn default_part_of_speech_dictionary() -> String {
+    "./dicts/alt12dicts-2020.12.07/2of12id.txt".to_string()
+}
+
+fn default_phrase_dictionary() -> String {
+    "./dicts/12dicts-6.0.2/Special/6phrase.txt".to_string()
+}
+
+fn default_proper_noun_dictionary() -> String {
+    "./dicts/alt12dicts-2020.12.07/5desk.txt".to_string()
+}
+
+fn default_signatures() -> Vec<String> {
+    [
+        "signature.txt",
+        "signature2.txt",
+        "signature3a.txt",
+        "signature3g.txt",
+        "signature4cmn.txt",
+        "signature4lem.txt",
+    ]
+    .into_iter()
+    .map(|file| format!("./dicts/alt12dicts-2020.12.07/{file}"))
+    .collect()
+}
+
+fn default_variant_dictionary() -> String {
+    "./dicts/alt12dicts-2020.12.07/variant.txt".to_string()
+}
+
+fn default_phonetic_dictionary() -> String {
+    "./dicts/12dicts-6.0.2/American/2of12inf.txt".to_string()
+}
+
+fn default_phonetic_distance() -> usize {
+    0
+}
+
+fn default_homophone_file() -> String {
+    "./assets/homophones.txt".to_string()
+}
+
+fn default_keyboard_layout() -> Vec<String> {
+    ["qwertyuiop", "asdfghjkl", "zxcvbnm"].map(String::from).to_vec()
+}
+
+// wrapping checkers only add to what a regular dictionary accepts
+fn default_wrapped_checker() -> Box<SpellCheckerConfig> {
+    Box::new(SpellCheckerConfig::Lemma {
+        dictionary: default_lemma_dictionary(),
+    })
 }
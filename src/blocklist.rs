@@ -0,0 +1,94 @@
+use crate::error::{read_file, Result};
+use crate::spellchecking::CheckResult;
+use itertools::Itertools;
+use std::cmp::max;
+use std::collections::HashSet;
+use types::BlocklistConfig;
+
+pub const BLOCKED: CheckResult = CheckResult::new(254);
+
+pub struct Blocklist {
+    // entries of several words are kept joined by single spaces
+    entries: HashSet<String, rapidhash::fast::RandomState>,
+    max_words: usize,
+    tag: bool,
+}
+
+impl Blocklist {
+    pub fn new(config: &BlocklistConfig) -> Result<Self> {
+        let mut entries = HashSet::with_hasher(rapidhash::fast::RandomState::new());
+        let mut max_words = 0;
+        for file in &config.files {
+            for line in read_file(file)?.lines() {
+                let line = line.trim();
+                if line.is_empty() || line.starts_with('#') {
+                    continue;
+                }
+                let words = line.split_whitespace().map(str::to_lowercase).collect_vec();
+                max_words = max(max_words, words.len());
+                entries.insert(words.join(" "));
+            }
+        }
+        Ok(Self {
+            entries,
+            max_words,
+            tag: config.tag,
+        })
+    }
+
+    // None when the mutation has to be dropped, entries the spell name already had aren't blocked
+    pub fn check(&self, processed: &str, mutation: &str, result: CheckResult) -> Option<CheckResult> {
+        if self.entries.is_empty() {
+            return Some(result);
+        }
+        let words = mutation.split(['$', ' ']).collect_vec();
+        let original_words = processed.split('$').collect_vec();
+        let mut original_spans = HashSet::new();
+        for len in 1..=self.max_words {
+            original_spans.extend(original_words.windows(len).map(|span| span.join(" ")));
+        }
+        let blocked = (1..=self.max_words).any(|len| {
+            words.windows(len).any(|span| {
+                let span = span.join(" ");
+                !original_spans.contains(&span) && self.entries.contains(&span)
+            })
+        });
+        match blocked {
+            false => Some(result),
+            true if self.tag => Some(BLOCKED),
+            true => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn blocklist(entries: &[&str], tag: bool) -> Blocklist {
+        Blocklist {
+            entries: entries.iter().map(|it| it.to_string()).collect(),
+            max_words: entries.iter().map(|it| it.split(' ').count()).max().unwrap_or(0),
+            tag,
+        }
+    }
+
+    #[test]
+    fn entries_span_words() {
+        let blocklist = blocklist(&["hex", "dark lord"], false);
+        let result = CheckResult::SUCCESS;
+        assert_eq!(blocklist.check("fire$bolt", "fire$hex", result), None);
+        assert_eq!(blocklist.check("dark$bolt", "dark$lord", result), None);
+        assert_eq!(blocklist.check("dark$bolt", "dar$klord", result), Some(result));
+        // a phrase can come out of a word split just as well
+        assert_eq!(blocklist.check("darklord", "dark lord", result), None);
+        assert_eq!(blocklist.check("dark$lord", "dark$lord$lord", result), Some(result));
+        assert_eq!(blocklist.check("hex$bolt", "hex$bolts", result), Some(result));
+    }
+
+    #[test]
+    fn tagged_entries_are_kept() {
+        let blocklist = blocklist(&["dark lord"], true);
+        assert_eq!(blocklist.check("dark$bolt", "dark$lord", CheckResult::SUCCESS), Some(BLOCKED));
+    }
+}
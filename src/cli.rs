@@ -0,0 +1,67 @@
+use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+use types::{MutationConfig, Spell};
+
+/// Mutates spell names into other words and phrases
+#[derive(Parser)]
+#[command(version)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Config file to load
+    #[arg(short, long, value_name = "FILE", default_value = "./config.json", global = true)]
+    pub config: PathBuf,
+
+    /// Maximum number of edits per mutation, overrides mutation_depth
+    #[arg(short, long, global = true)]
+    pub depth: Option<usize>,
+
+    /// Directory to write results to, overrides output_dir
+    #[arg(short, long, value_name = "DIR", global = true)]
+    pub output_dir: Option<String>,
+
+    /// Spell list to mutate, overrides input_file
+    #[arg(short, long, value_name = "FILE", global = true)]
+    pub input_file: Option<String>,
+
+    /// Only run the named spell checker, can be repeated
+    #[arg(long = "checker", value_name = "NAME", global = true)]
+    pub checkers: Vec<String>,
+
+    /// Only mutate spells whose name contains this text
+    #[arg(short, long, value_name = "TEXT", global = true)]
+    pub filter: Option<String>,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Find the spells that can mutate into a word or phrase
+    Reverse { target: String },
+    /// Mutate a single phrase instead of the spell list
+    Phrase { phrase: String },
+}
+
+impl Cli {
+    pub fn apply(&self, config: &mut MutationConfig) {
+        if let Some(depth) = self.depth {
+            config.mutation_depth = depth;
+        }
+        if let Some(output_dir) = &self.output_dir {
+            config.output_dir = output_dir.clone();
+        }
+        if let Some(input_file) = &self.input_file {
+            config.input_file = input_file.clone();
+        }
+    }
+
+    pub fn runs_checker(&self, name: &str) -> bool {
+        self.checkers.is_empty() || self.checkers.iter().any(|it| it.eq_ignore_ascii_case(name))
+    }
+
+    pub fn includes_spell(&self, spell: &Spell) -> bool {
+        self.filter.as_ref().is_none_or(|filter| {
+            spell.name.to_lowercase().contains(&filter.to_lowercase())
+        })
+    }
+}
@@ -1,15 +1,29 @@
 use crate::spellchecking::CheckResult;
 use dashmap::DashMap;
 use itertools::Itertools;
+use std::cmp::Reverse;
 use std::collections::{HashMap, HashSet};
 use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use humantime::format_duration;
 use types::MutationConfig;
 
+#[derive(Default)]
 pub struct Diagnostics {
     pub initial_spell_count: usize,
     initial_word_usage: DashMap<String, usize>,
     word_splits: DashMap<String, HashSet<(CheckResult, String)>>,
+    search_levels: DashMap<usize, SearchLevel>,
     pub final_spell_count: AtomicUsize,
+    blocked: AtomicUsize,
+}
+
+#[derive(Default)]
+struct SearchLevel {
+    checked: usize,
+    duplicates: usize,
+    pruned: usize,
+    time: Duration,
 }
 
 impl Diagnostics {
@@ -18,7 +32,9 @@ impl Diagnostics {
             initial_spell_count: 0,
             initial_word_usage: Default::default(),
             word_splits: Default::default(),
+            search_levels: Default::default(),
             final_spell_count: AtomicUsize::default(),
+            blocked: AtomicUsize::default(),
         }
     }
 
@@ -34,6 +50,25 @@ impl Diagnostics {
             .insert((check_result, split));
     }
 
+    pub fn log_blocked(&self) {
+        self.blocked.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn log_search_level(
+        &self,
+        depth: usize,
+        checked: usize,
+        duplicates: usize,
+        pruned: usize,
+        time: Duration,
+    ) {
+        let mut level = self.search_levels.entry(depth).or_default();
+        level.checked += checked;
+        level.duplicates += duplicates;
+        level.pruned += pruned;
+        level.time += time;
+    }
+
     pub fn stringify(&self, config: &MutationConfig, verbose: bool) -> String {
         let mut lines: Vec<String> = vec![];
 
@@ -43,6 +78,8 @@ impl Diagnostics {
             "final spell count: {}",
             self.final_spell_count.load(Ordering::Relaxed)
         ));
+        lines.push(format!("blocked mutations: {}", self.blocked.load(Ordering::Relaxed)));
+        self.search_levels(&mut lines);
         if config.advanced_diagnostics {
             self.advanced_diagnostics(&mut lines, verbose);
         }
@@ -60,6 +97,23 @@ impl Diagnostics {
         }
     }
 
+    fn search_levels(&self, lines: &mut Vec<String>) {
+        lines.push("\nsearch levels:".to_string());
+
+        let mut levels = self.search_levels.iter().collect_vec();
+        levels.sort_unstable_by_key(|it| *it.key());
+        for level in levels {
+            lines.push(format!(
+                "- depth {}: {} strings checked, {} duplicates skipped, {} pruned, {} cpu time",
+                level.key() + 1,
+                level.checked,
+                level.duplicates,
+                level.pruned,
+                format_duration(Duration::from_millis(level.time.as_millis() as u64)),
+            ));
+        }
+    }
+
     fn initial_word_counts(&self, lines: &mut Vec<String>, verbose: bool) {
         // most used initial words
         lines.push("\nmost used words:".to_string());
@@ -72,7 +126,7 @@ impl Diagnostics {
                 .push(it.key().clone());
         }
         let mut words_by_count = words_by_count.into_iter().collect::<Vec<_>>();
-        words_by_count.sort_unstable_by(|first, second| second.0.cmp(&first.0));
+        words_by_count.sort_unstable_by_key(|(count, _)| Reverse(*count));
 
         let mut total_vertical = 0;
         for (count, words) in words_by_count {
@@ -119,13 +173,12 @@ fn split_words(
     lines: &mut Vec<String>,
     split: &DashMap<String, HashSet<(CheckResult, String)>>,
 ) {
-    let mut split = split
+    let split = split
         .iter()
         .collect_vec();
     let mut split = split.iter()
-        .map(|it| it.value().iter()
+        .flat_map(|it| it.value().iter()
             .map(|(check, split)| (check, it.key().as_str(), split.as_str())))
-        .flatten()
         .collect_vec();
     split.sort_unstable();
     for (check, original, split) in split {
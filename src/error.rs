@@ -0,0 +1,126 @@
+use peg::error::ParseError;
+use peg::str::LineCol;
+use serde::de::DeserializeOwned;
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::{fmt, fs, io};
+use types::MutationConfig;
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+#[derive(Debug)]
+pub enum Error {
+    Io {
+        path: PathBuf,
+        source: io::Error,
+    },
+    Json {
+        path: PathBuf,
+        source: serde_json::Error,
+    },
+    Dictionary {
+        path: PathBuf,
+        source: ParseError<LineCol>,
+    },
+    Language {
+        path: PathBuf,
+        source: zspell::Error,
+    },
+    UnsupportedCharacter {
+        name: String,
+        character: char,
+    },
+    UnknownSpellChecker(String),
+    DuplicateSpellChecker(String),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io { path, source } => write!(f, "failed to access {}: {source}", path.display()),
+            Error::Json { path, source } => write!(f, "failed to parse {}: {source}", path.display()),
+            Error::Dictionary { path, source } => write!(
+                f,
+                "failed to parse dictionary {} at line {}, column {}: expected {}",
+                path.display(),
+                source.location.line,
+                source.location.column,
+                source.expected
+            ),
+            Error::Language { path, source } => {
+                write!(f, "failed to load language {}: {source}", path.display())
+            }
+            Error::UnsupportedCharacter { name, character } => {
+                write!(f, "unsupported character {character:?} in \"{name}\"")
+            }
+            Error::UnknownSpellChecker(name) => write!(f, "spell checker \"{name}\" isn't configured"),
+            Error::DuplicateSpellChecker(name) => {
+                write!(f, "spell checker \"{name}\" is configured twice, give one of them a label")
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            Error::Json { source, .. } => Some(source),
+            Error::Dictionary { source, .. } => Some(source),
+            Error::Language { source, .. } => Some(source),
+            Error::UnsupportedCharacter { .. }
+            | Error::UnknownSpellChecker(_)
+            | Error::DuplicateSpellChecker(_) => None,
+        }
+    }
+}
+
+pub fn read_file(path: impl AsRef<Path>) -> Result<String> {
+    let path = path.as_ref();
+    fs::read_to_string(path).map_err(|source| Error::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+pub fn write_file(path: impl AsRef<Path>, contents: impl AsRef<[u8]>) -> Result<()> {
+    let path = path.as_ref();
+    fs::write(path, contents).map_err(|source| Error::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+pub fn create_dir(path: impl AsRef<Path>) -> Result<()> {
+    let path = path.as_ref();
+    fs::create_dir_all(path).map_err(|source| Error::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+pub fn read_config(path: impl AsRef<Path>) -> Result<MutationConfig> {
+    let path = path.as_ref();
+    let mut config: MutationConfig = read_json(path)?;
+    config.resolve_paths(path.parent().unwrap_or(Path::new("")));
+    Ok(config)
+}
+
+pub fn read_json<T: DeserializeOwned>(path: impl AsRef<Path>) -> Result<T> {
+    let path = path.as_ref();
+    serde_json::from_str(&read_file(path)?).map_err(|source| Error::Json {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+pub fn parse_dictionary<'a, T>(
+    path: &str,
+    contents: &'a str,
+    parser: impl FnOnce(&'a str) -> Result<T, ParseError<LineCol>>,
+) -> Result<T> {
+    parser(contents).map_err(|source| Error::Dictionary {
+        path: PathBuf::from(path),
+        source,
+    })
+}
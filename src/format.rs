@@ -1,67 +1,195 @@
-use crate::spellchecking::CheckResult;
+use crate::error::{create_dir, write_file, Result};
+use crate::mutation::MutationResult;
 use dashmap::DashMap;
 use itertools::Itertools;
-use std::collections::{HashMap, HashSet};
-use std::fs;
-use std::fs::File;
-use std::io::Write;
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::mem;
 use std::path::PathBuf;
 use std::str::FromStr;
-use types::{MutationConfig, Spell, MUTATED_SPELLS_FILE, MUTATED_SPELLS_JSON};
+use std::sync::Arc;
+use types::{MutationConfig, Spell};
+
+const NO_MUTATIONS: &str = "no mutations";
+const MAX_LINE_LENGTH: usize = 80;
+
+pub type SpellMutations = HashMap<String, HashMap<String, MutationResult>>;
 
 pub fn format_mutations(
     spells: Vec<Spell>,
-    mutations: DashMap<&'static str, HashMap<String, HashMap<String, (CheckResult, usize)>>>,
+    mutations: DashMap<Arc<str>, SpellMutations>,
     config: MutationConfig,
-) {
+) -> Result<()> {
     let mut output = PathBuf::from_str(&config.output_dir).unwrap();
     for (checker_name, mut mutations) in mutations {
-        output.push(checker_name);
-        fs::create_dir(&output);
-        let mut output_files = vec![];
-        for depth in 1..=config.mutation_depth {
-            output.push(format!("{depth} deep mutated spells.txt"));
-            output_files.push(File::create(&output).expect("Failed to open output file"));
-            output.pop();
-        }
-        output.pop();
+        let mut output_files = vec![String::new(); config.mutation_depth];
         for spell in &spells {
-            if let Some(mutations) = mutations.remove(&spell.name) {
-                let mut mutations = mutations
-                    .into_iter()
-                    .fold(HashMap::new(), |mut acc, (mutation, (check, depth))| {
-                        acc.entry(check)
-                            .or_insert_with(HashMap::new)
-                            .entry(depth)
-                            .or_insert_with(Vec::new)
-                            .push(mutation);
-                        acc
-                    })
-                    .into_iter()
-                    .map(|(depth, checked)| (depth, checked.into_iter().collect_vec()))
-                    .collect_vec();
-                mutations.sort_unstable_by_key(|(x, _)| *x);
-                let mut empties = vec![true; config.mutation_depth];
+            let mut mutations = mutations
+                .remove(&spell.name)
+                .unwrap_or_default()
+                .into_iter()
+                .fold(HashMap::new(), |mut acc, (mutation, result)| {
+                    acc.entry(result.rank())
+                        .or_insert_with(HashMap::new)
+                        .entry(result.depth)
+                        .or_insert_with(Vec::new)
+                        .push((mutation, result));
+                    acc
+                })
+                .into_iter()
+                .map(|(depth, checked)| (depth, checked.into_iter().collect_vec()))
+                .collect_vec();
+            mutations.sort_unstable_by_key(|(x, _)| *x);
+            let mut empties = vec![true; config.mutation_depth];
 
-                for (check, mutations) in &mut mutations {
-                    mutations.sort_unstable_by_key(|(depth, _)| *depth);
-                    for (depth, mutations) in mutations {
-                        mutations.sort_unstable();
-                        for (depth_idx, target) in 
-                            output_files[*depth..].iter_mut().enumerate() {
-                            if empties[depth_idx] {
-                                target
-                                    .write_all(spell.write_spell_information().as_bytes())
-                                    .unwrap();
-                                empties[depth_idx] = false;
-                            }
-                            for mutation in mutations.iter() {
-                                write!(target, "{}{}\n", check, mutation).unwrap();
-                            }
+            for (_, mutations) in &mut mutations {
+                mutations.sort_unstable_by_key(|(depth, _)| *depth);
+                for (depth, mutations) in mutations {
+                    mutations.sort_unstable_by(|(first, _), (second, _)| first.cmp(second));
+                    for (depth_idx, target) in output_files.iter_mut().enumerate().skip(*depth) {
+                        if empties[depth_idx] {
+                            target.push_str(&spell.write_spell_information());
+                            empties[depth_idx] = false;
+                        }
+                        for (mutation, result) in mutations.iter() {
+                            writeln!(target, "{}", format_mutation(mutation, result, &config))
+                                .unwrap();
                         }
                     }
                 }
             }
+
+            if !config.omit_zero_mutation_spells {
+                for (target, _) in output_files.iter_mut().zip(empties).filter(|(_, it)| *it) {
+                    target.push_str(&spell.write_spell_information());
+                    writeln!(target, "{NO_MUTATIONS}").unwrap();
+                }
+            }
+        }
+
+        output.push(&*checker_name);
+        create_dir(&output)?;
+        for (depth, contents) in output_files.iter().enumerate() {
+            output.push(format!("{} deep mutated spells.txt", depth + 1));
+            write_file(&output, contents)?;
+            output.pop();
+        }
+        output.pop();
+    }
+    Ok(())
+}
+
+pub fn format_word_mutations(mutations: SpellMutations) -> String {
+    let mut words = mutations.into_iter().collect_vec();
+    words.sort_unstable_by(|(first, _), (second, _)| first.cmp(second));
+
+    let mut lines = vec![];
+    for (word, mutations) in words {
+        lines.push(format!("\n{word}:"));
+        let mut mutations = mutations.into_iter().collect_vec();
+        mutations.sort_unstable_by(|(first, first_result), (second, second_result)| {
+            (first_result.depth, first_result.rank(), first)
+                .cmp(&(second_result.depth, second_result.rank(), second))
+        });
+        for (depth, mutations) in &mutations.iter().chunk_by(|(_, result)| result.depth) {
+            let mut line = format!("- {} deep:", depth + 1);
+            for (mutation, result) in mutations {
+                let entry = format!("{}{}", result.check, display_mutation(mutation, result));
+                if line.len() + entry.len() + 2 > MAX_LINE_LENGTH {
+                    lines.push(mem::take(&mut line));
+                    line.push_str("   ");
+                }
+                line.push(' ');
+                line.push_str(&entry);
+                line.push(',');
+            }
+            line.pop();
+            lines.push(line);
         }
     }
+    lines.join("\n")
+}
+
+pub fn format_spoonerisms(spells: &[Spell], mut spoonerisms: SpellMutations) -> String {
+    let mut output = String::new();
+    for spell in spells {
+        let Some(spoonerisms) = spoonerisms.remove(&spell.name) else {
+            continue;
+        };
+        output.push_str(&spell.write_spell_information());
+        output.push_str(&format_spoonerism_list(spoonerisms));
+    }
+    output
+}
+
+pub fn format_spoonerism_list(spoonerisms: HashMap<String, MutationResult>) -> String {
+    let mut output = String::new();
+    let spoonerisms = spoonerisms
+        .into_iter()
+        .sorted_unstable_by(|(first, first_result), (second, second_result)| {
+            (first_result.rank(), first).cmp(&(second_result.rank(), second))
+        });
+    for (spoonerism, result) in spoonerisms {
+        writeln!(output, "{}{}{}", result.check, phrase_label(&result), spoonerism).unwrap();
+    }
+    output
+}
+
+pub fn format_phrase_mutations(
+    mutations: HashMap<String, MutationResult>,
+    config: &MutationConfig,
+) -> String {
+    let mut mutations = mutations.into_iter().collect_vec();
+    mutations.sort_unstable_by(|(first, first_result), (second, second_result)| {
+        (first_result.depth, first_result.rank(), first)
+            .cmp(&(second_result.depth, second_result.rank(), second))
+    });
+
+    let mut lines = vec![];
+    for (depth, mutations) in &mutations.iter().chunk_by(|(_, result)| result.depth) {
+        lines.push(format!("\n{} deep:", depth + 1));
+        for (mutation, result) in mutations {
+            lines.push(format_mutation(mutation, result, config));
+        }
+    }
+    lines.join("\n")
+}
+
+fn format_mutation(mutation: &str, result: &MutationResult, config: &MutationConfig) -> String {
+    // only letter edits have a path to show
+    if config.show_edit_paths && !result.path.is_empty() {
+        format!(
+            "{}{} <- {}",
+            result.check,
+            display_mutation(mutation, result),
+            result.steps(mutation).iter().rev().skip(1).join(" <- ")
+        )
+    } else {
+        format!("{}{}", result.check, display_mutation(mutation, result))
+    }
+}
+
+fn display_mutation(mutation: &str, result: &MutationResult) -> String {
+    // mutations into proper nouns read better capitalized, like the spell names themselves
+    let mutation = mutation
+        .split(' ')
+        .enumerate()
+        .map(|(index, word)| {
+            if !result.capitalized.contains(&index) {
+                return word.to_string();
+            }
+            let (first, rest) = word.split_at(word.len().min(1));
+            first.to_ascii_uppercase() + rest
+        })
+        .join(" ");
+    let typo = result.typo.map(|typo| format!("~{typo} ")).unwrap_or_default();
+    format!("{}{}{}{}", typo, phrase_label(result), result.kind, mutation)
+}
+
+fn phrase_label(result: &MutationResult) -> &'static str {
+    if result.phrase {
+        "[phrase] "
+    } else {
+        ""
+    }
 }
@@ -0,0 +1,156 @@
+use std::cmp::min;
+use std::collections::HashMap;
+
+// bk-tree over processed spell names, every node holds the spells sharing its name
+pub struct SpellIndex {
+    nodes: Vec<IndexNode>,
+}
+
+struct IndexNode {
+    name: String,
+    spells: Vec<usize>,
+    children: HashMap<usize, usize>,
+}
+
+impl SpellIndex {
+    pub fn new(names: impl IntoIterator<Item = (usize, String)>) -> Self {
+        let mut index = Self { nodes: vec![] };
+        for (spell, name) in names {
+            index.insert(spell, name);
+        }
+        index
+    }
+
+    fn insert(&mut self, spell: usize, name: String) {
+        if self.nodes.is_empty() {
+            self.nodes.push(IndexNode::new(spell, name));
+            return;
+        }
+        let mut node = 0;
+        loop {
+            let distance = levenshtein(self.nodes[node].name.as_bytes(), name.as_bytes());
+            if distance == 0 {
+                self.nodes[node].spells.push(spell);
+                return;
+            }
+            match self.nodes[node].children.get(&distance) {
+                Some(child) => node = *child,
+                None => {
+                    let child = self.nodes.len();
+                    self.nodes[node].children.insert(distance, child);
+                    self.nodes.push(IndexNode::new(spell, name));
+                    return;
+                }
+            }
+        }
+    }
+
+    pub fn find(&self, target: &str, max_distance: usize) -> Vec<(&str, &[usize])> {
+        let mut found = vec![];
+        if self.nodes.is_empty() {
+            return found;
+        }
+        let mut pending = vec![0];
+        while let Some(node) = pending.pop() {
+            let node = &self.nodes[node];
+            let distance = levenshtein(node.name.as_bytes(), target.as_bytes());
+            if distance <= max_distance {
+                found.push((node.name.as_str(), node.spells.as_slice()));
+            }
+            for (child_distance, child) in &node.children {
+                if child_distance.abs_diff(distance) <= max_distance {
+                    pending.push(*child);
+                }
+            }
+        }
+        found
+    }
+}
+
+impl IndexNode {
+    fn new(spell: usize, name: String) -> Self {
+        Self {
+            name,
+            spells: vec![spell],
+            children: HashMap::new(),
+        }
+    }
+}
+
+// processed names separate words with '$' where the original had a space, both compare equal
+pub fn levenshtein(first: &[u8], second: &[u8]) -> usize {
+    let mut row = (0..=second.len()).collect::<Vec<_>>();
+    for (first_idx, first_letter) in first.iter().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = first_idx + 1;
+        for (second_idx, second_letter) in second.iter().enumerate() {
+            let substitution = diagonal + !same_letter(*first_letter, *second_letter) as usize;
+            diagonal = row[second_idx + 1];
+            row[second_idx + 1] = min(min(row[second_idx], row[second_idx + 1]) + 1, substitution);
+        }
+    }
+    row[second.len()]
+}
+
+pub fn same_letter(first: u8, second: u8) -> bool {
+    let separator = |letter| if letter == b'$' { b' ' } else { letter };
+    separator(first) == separator(second)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn levenshtein_counts_edits() {
+        assert_eq!(levenshtein(b"kitten", b"sitting"), 3);
+        assert_eq!(levenshtein(b"", b"bolt"), 4);
+        assert_eq!(levenshtein(b"bolt", b"bolt"), 0);
+        assert_eq!(levenshtein(b"bolt", b"blot"), 2);
+    }
+
+    #[test]
+    fn word_separators_match_spaces() {
+        assert_eq!(levenshtein(b"fire$bolt", b"fire bolt"), 0);
+        assert_eq!(levenshtein(b"fire$bolt", b"firebolt"), 1);
+    }
+
+    #[test]
+    fn find_returns_every_name_within_the_distance() {
+        let names = ["fire bolt", "fire bolt", "fire ball", "firebolt", "wish", "cure wounds"];
+        let index = SpellIndex::new(names.iter().map(|it| it.to_string()).enumerate());
+
+        let mut found = index.find("fire bolt", 0);
+        assert_eq!(found, vec![("fire bolt", &[0, 1][..])]);
+
+        found = index.find("fire bolt", 2);
+        found.sort_unstable();
+        assert_eq!(
+            found,
+            vec![("fire ball", &[2][..]), ("fire bolt", &[0, 1][..]), ("firebolt", &[3][..])]
+        );
+        assert!(index.find("magic missile", 3).is_empty());
+    }
+
+    #[test]
+    fn find_matches_a_brute_force_search() {
+        let names = ["wish", "wash", "fish", "fist", "mist", "missile", "mile", "smile", "wit"];
+        let index = SpellIndex::new(names.iter().map(|it| it.to_string()).enumerate());
+        for target in ["wish", "mile", "west", "mist", "a"] {
+            for max_distance in 0..4 {
+                let mut found = index
+                    .find(target, max_distance)
+                    .into_iter()
+                    .flat_map(|(_, spells)| spells.iter().copied())
+                    .collect::<Vec<_>>();
+                found.sort_unstable();
+                let expected = (0..names.len())
+                    .filter(|idx| {
+                        levenshtein(names[*idx].as_bytes(), target.as_bytes()) <= max_distance
+                    })
+                    .collect::<Vec<_>>();
+                assert_eq!(found, expected, "{target} within {max_distance}");
+            }
+        }
+    }
+}
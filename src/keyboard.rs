@@ -0,0 +1,191 @@
+use crate::mutation::{edit_steps, Edit};
+use itertools::Itertools;
+use types::{KeyboardConfig, MutationOperator};
+
+pub struct Keyboard {
+    // row and column of every key, columns count half keys since the rows are staggered
+    keys: [Option<(usize, usize)>; 256],
+    rows: usize,
+    // the distance of keys missing from the layout, further than any two keys on it
+    far: usize,
+    restrict: bool,
+}
+
+impl Keyboard {
+    pub fn new(config: &KeyboardConfig) -> Self {
+        let mut keys = [None; 256];
+        for (row, letters) in config.layout.iter().enumerate() {
+            for (column, letter) in letters.bytes().enumerate() {
+                keys[letter as usize] = Some((row, 2 * column + row));
+            }
+        }
+        let far = config.layout.len() + config.layout.iter().map(String::len).max().unwrap_or(0);
+        Self {
+            keys,
+            rows: config.layout.len(),
+            far,
+            restrict: config.restrict,
+        }
+    }
+
+    pub fn restricted(&self) -> bool {
+        self.restrict
+    }
+
+    // how many keys a finger has to move from one key to the other
+    fn distance(&self, first: u8, second: u8) -> usize {
+        // words are split by spaces as well as '$'
+        let is_space = |letter: u8| letter == b' ' || letter == b'$';
+        if first == second || is_space(first) && is_space(second) {
+            return 0;
+        }
+        match (self.keys[first as usize], self.keys[second as usize]) {
+            (Some((first_row, first_column)), Some((second_row, second_column))) => {
+                let rows = first_row.abs_diff(second_row);
+                rows + first_column.abs_diff(second_column).saturating_sub(rows) / 2
+            }
+            // the space bar runs below the whole bottom row
+            (Some((row, _)), None) if is_space(second) => self.rows - row,
+            (None, Some((row, _))) if is_space(first) => self.rows - row,
+            _ => self.far,
+        }
+    }
+
+    // how far the key of a changed or added letter is from the key that was meant, letters
+    // are only ever added by hitting a key next to the one before or after them
+    fn edit_distance(&self, edit: &Edit, letters: &[u8]) -> usize {
+        match edit.operator {
+            MutationOperator::Change => {
+                self.distance(edit.old.unwrap() as u8, edit.new.unwrap() as u8)
+            }
+            MutationOperator::Add => {
+                let letter = letters[edit.position];
+                let before = edit.position.checked_sub(1).map(|idx| letters[idx]);
+                let after = letters.get(edit.position + 1).copied();
+                before
+                    .into_iter()
+                    .chain(after)
+                    .map(|neighbour| self.distance(neighbour, letter))
+                    .min()
+                    .unwrap_or(self.far)
+            }
+            // a missed key or two keys hit out of order don't depend on where the keys are
+            _ => 0,
+        }
+    }
+
+    // nobody mistypes a whole word
+    pub fn allows(&self, edit: &Edit, mutation: &[u8]) -> bool {
+        !self.restrict
+            || (!edit.operator.is_word_level() && self.edit_distance(edit, mutation) <= 1)
+    }
+
+    // the sum of how far every changed or added letter is from the key that was meant, lower
+    // scores are likelier typos, paths with word level edits aren't typos at all
+    pub fn score(&self, path: &[Edit], mutation: &str) -> Option<usize> {
+        if path.iter().any(|edit| edit.operator.is_word_level()) {
+            return None;
+        }
+        let steps = edit_steps(path, mutation);
+        let score = path
+            .iter()
+            .zip_eq(&steps[1..])
+            .map(|(edit, step)| self.edit_distance(edit, step.as_bytes()))
+            .sum();
+        Some(score)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keyboard(restrict: bool) -> Keyboard {
+        let layout = ["qwertyuiop", "asdfghjkl", "zxcvbnm"].map(String::from).to_vec();
+        Keyboard::new(&KeyboardConfig { layout, restrict })
+    }
+
+    fn edit(
+        operator: MutationOperator,
+        position: usize,
+        old: Option<char>,
+        new: Option<char>,
+    ) -> Edit {
+        Edit {
+            operator,
+            position,
+            old,
+            new,
+            word: None,
+        }
+    }
+
+    #[test]
+    fn distance_counts_keys_between() {
+        let keyboard = keyboard(false);
+        assert_eq!(keyboard.distance(b'f', b'f'), 0);
+        assert_eq!(keyboard.distance(b'f', b'g'), 1);
+        assert_eq!(keyboard.distance(b'q', b'a'), 1);
+        assert_eq!(keyboard.distance(b'w', b'a'), 1);
+        assert_eq!(keyboard.distance(b'q', b'p'), 9);
+        assert_eq!(keyboard.distance(b'q', b'm'), 8);
+    }
+
+    #[test]
+    fn missing_keys_are_far() {
+        let keyboard = keyboard(false);
+        assert_eq!(keyboard.distance(b'q', b'1'), keyboard.far);
+        assert_eq!(keyboard.distance(b'-', b'q'), keyboard.far);
+        assert!((b'a'..=b'z').all(|letter| keyboard.distance(b'q', letter) < keyboard.far));
+    }
+
+    #[test]
+    fn space_bar_is_below_the_bottom_row() {
+        let keyboard = keyboard(false);
+        assert_eq!(keyboard.distance(b'm', b' '), 1);
+        assert_eq!(keyboard.distance(b'$', b'a'), 2);
+        assert_eq!(keyboard.distance(b'q', b' '), 3);
+        assert_eq!(keyboard.distance(b' ', b'$'), 0);
+    }
+
+    #[test]
+    fn score_sums_edit_distances() {
+        let keyboard = keyboard(false);
+        let change = edit(MutationOperator::Change, 0, Some('f'), Some('g'));
+        assert_eq!(keyboard.score(std::slice::from_ref(&change), "gire$bolt"), Some(1));
+        let far_change = edit(MutationOperator::Change, 5, Some('b'), Some('p'));
+        assert_eq!(keyboard.score(&[change, far_change], "gire$polt"), Some(6));
+        // only the closer of the letters around an added one counts
+        let add = edit(MutationOperator::Add, 1, None, Some(' '));
+        assert_eq!(keyboard.score(&[add], "a fire$bolt"), Some(2));
+        let remove = edit(MutationOperator::Remove, 1, Some('i'), None);
+        assert_eq!(keyboard.score(&[remove], "fre$bolt"), Some(0));
+        let drop = Edit {
+            word: Some("fire$".to_string()),
+            ..edit(MutationOperator::DropWord, 0, None, None)
+        };
+        assert_eq!(keyboard.score(&[drop], "bolt"), None);
+    }
+
+    #[test]
+    fn restricted_keyboard_allows_neighbouring_keys() {
+        let drop = Edit {
+            word: Some("fire$".to_string()),
+            ..edit(MutationOperator::DropWord, 0, None, None)
+        };
+        let near = edit(MutationOperator::Change, 0, Some('f'), Some('g'));
+        let far = edit(MutationOperator::Change, 0, Some('f'), Some('p'));
+        let missing = edit(MutationOperator::Change, 0, Some('f'), Some('1'));
+
+        let restricted = keyboard(true);
+        assert!(restricted.allows(&near, b"gire$bolt"));
+        assert!(!restricted.allows(&far, b"pire$bolt"));
+        assert!(!restricted.allows(&missing, b"1ire$bolt"));
+        assert!(!restricted.allows(&drop, b"bolt"));
+
+        let unrestricted = keyboard(false);
+        for (edit, mutation) in [(&near, "gire$bolt"), (&far, "pire$bolt"), (&drop, "bolt")] {
+            assert!(unrestricted.allows(edit, mutation.as_bytes()));
+        }
+    }
+}
@@ -0,0 +1,16 @@
+pub mod blocklist;
+pub mod diagnostics;
+pub mod error;
+pub mod format;
+pub mod index;
+pub mod keyboard;
+pub mod mutation;
+pub mod spellchecking;
+
+pub use error::{Error, Result};
+pub use mutation::{
+    normalize_name, Edit, MutationContext, MutationKind, MutationResult, MutationTarget,
+    PhraseMutations, ReverseMatch,
+};
+pub use spellchecking::{build_spell_checker, CheckResult, SpellChecker};
+pub use types;
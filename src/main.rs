@@ -1,93 +1,223 @@
-#![allow(unused)]
-
-use crate::format::format_mutations;
-use crate::mutation::{MutationContext, MutationTarget};
-use crate::spellchecking::lemma::LemmaSpellChecker;
-use crate::spellchecking::old::OldSpellChecker;
-use crate::spellchecking::SpellChecker;
-use dashmap::mapref::one::RefMut;
+use crate::cli::{Cli, Command};
 use dashmap::DashMap;
 use humantime::format_duration;
+use itertools::Itertools;
 use rayon::prelude::*;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
+use std::process::ExitCode;
 use std::str::FromStr;
 use std::sync::atomic::Ordering;
 use std::time::Instant;
-use std::{fs, mem};
+use clap::Parser;
+use std::mem;
 use std::sync::Arc;
-use types::{MutationConfig, Spell, DIAGNOSTICS_FILE, MUTATED_SPELLS_JSON, MUTATED_WORDS_FILE};
-use crate::spellchecking::freq::FreqSpellChecker;
+use spell_mutator::format::{
+    format_mutations, format_phrase_mutations, format_spoonerism_list, format_spoonerisms,
+    format_word_mutations, SpellMutations,
+};
+use spell_mutator::error::{create_dir, read_config, read_json, write_file};
+use spell_mutator::{normalize_name, Error, MutationContext, PhraseMutations, Result};
+use types::{
+    MutationConfig, Spell, DIAGNOSTICS_FILE, MUTATED_SPELLS_JSON, MUTATED_WORDS_FILE,
+    SPOONERISMS_FILE, SPOONERISMS_JSON,
+};
 
-mod diagnostics;
-mod format;
-mod mutation;
-mod spellchecking;
+mod cli;
 
-fn main() {
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    if let Err(err) = run(&cli) {
+        eprintln!("error: {err}");
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
+
+fn run(cli: &Cli) -> Result<()> {
+    let mut config = parse_config(cli)?;
+    cli.apply(&mut config);
+
+    match &cli.command {
+        Some(Command::Reverse { target }) => reverse_search(config, cli, target),
+        Some(Command::Phrase { phrase }) => mutate_phrase(config, cli, phrase),
+        None => mutate_spells(config, cli),
+    }
+}
+
+fn build_context(mut config: MutationConfig, cli: &Cli) -> Result<MutationContext> {
+    for name in &cli.checkers {
+        if !config.spell_checkers.iter().any(|it| it.name().eq_ignore_ascii_case(name)) {
+            return Err(Error::UnknownSpellChecker(name.clone()));
+        }
+    }
+
+    config.spell_checkers.retain(|checker| cli.runs_checker(checker.name()));
+    MutationContext::from_config(config)
+}
+
+fn reverse_search(config: MutationConfig, cli: &Cli, target: &str) -> Result<()> {
+    let spells = parse_spells(&config, cli)?;
+    let depth = config.mutation_depth;
+    let ctx = build_context(config, cli)?;
+
+    let matches = ctx.reverse_search(&spells, target, depth)?;
+    println!("{} spells can mutate into \"{}\" within {} edits:", matches.len(), target, depth);
+    let target = ctx.processed_name(target)?;
+    for spell_match in matches {
+        println!("\n{}", spell_match.spell.name);
+        for (checker, result) in spell_match.results {
+            println!(
+                "- {}: {}{} deep, {}",
+                checker,
+                result.check,
+                result.depth + 1,
+                result.steps(&target).join(" -> ")
+            );
+        }
+    }
+    Ok(())
+}
+
+fn mutate_phrase(config: MutationConfig, cli: &Cli, phrase: &str) -> Result<()> {
+    let ctx = build_context(config, cli)?;
+
+    for PhraseMutations { checker, mutations, spoonerisms } in ctx.mutate_phrase(phrase)? {
+        println!(
+            "\n\n{} mutations of \"{}\" for {}:",
+            mutations.len(),
+            phrase,
+            checker
+        );
+        println!("{}", format_phrase_mutations(mutations, &ctx.config));
+        if !spoonerisms.is_empty() {
+            println!("\n{} spoonerisms:", spoonerisms.len());
+            print!("{}", format_spoonerism_list(spoonerisms));
+        }
+    }
+    Ok(())
+}
+
+fn mutate_spells(config: MutationConfig, cli: &Cli) -> Result<()> {
     let start_time = Instant::now();
-    let (config, mut spells) = parse_files();
-
-    let lemma_target = MutationTarget::new(
-        Box::new(LemmaSpellChecker::new()) as Box<dyn SpellChecker>);
-    let freq_target = MutationTarget::new(
-        Box::new(FreqSpellChecker::new()) as Box<dyn SpellChecker>);
-    // let legacy_target = MutationTarget::new(
-    //     Box::new(OldSpellChecker::new()) as Box<dyn SpellChecker>);
+    let spells = parse_spells(&config, cli)?;
+
+    let mut ctx = build_context(config, cli)?;
     let spell_checker_init_end_time = Instant::now();
-    let mut ctx = MutationContext::new(config, vec![lemma_target, freq_target]);
-    let mut mutations: DashMap<&'static str, HashMap<_, _>> = DashMap::new();
+    let mutations: DashMap<Arc<str>, HashMap<_, _>> = DashMap::new();
+    let spoonerisms: DashMap<Arc<str>, SpellMutations> = DashMap::new();
     
     for target in &mut ctx.targets {
         target.diagnostics.initial_spell_count = spells.len();
+        mutations.insert(target.name.clone(), HashMap::new());
     }
 
-    spells.par_iter().for_each(|spell| {
-        let mutation_name = spell
-            .name
-            .chars()
-            .filter(|c| *c != '\'')
-            .map(|c| c.to_ascii_lowercase())
-            .map(|c| if !c.is_ascii_alphabetic() { ' ' } else { c })
-            .collect::<String>();
+    // reject bad spell names before spending any time on mutations
+    let names = spells
+        .iter()
+        .map(|spell| ctx.processed_name(&spell.name))
+        .collect::<Result<Vec<_>>>()?;
+
+    spells.par_iter().try_for_each(|spell| {
+        let mutation_name = normalize_name(&spell.name)?;
+        ctx.log_initial_words(&mutation_name)?;
         ctx.mutate(
             &mutation_name,
             ctx.config.mutation_depth,
-        );
+        )?;
         for target in &ctx.targets {
-            if let Some(results) = target.take_mutations(&mutation_name) {
-                target.diagnostics.final_spell_count.fetch_add(results.len(), Ordering::Relaxed);
-                mutations.entry(target.spellchecker.name())
+            let results = target.take_mutations(&mutation_name).unwrap_or_default();
+            if let Some(spell_spoonerisms) = target.take_spoonerisms(&mutation_name) {
+                spoonerisms.entry(target.name.clone())
+                    .or_default().value_mut()
+                    .insert(spell.name.clone(), spell_spoonerisms);
+            }
+            target.diagnostics.final_spell_count.fetch_add(results.len(), Ordering::Relaxed);
+            if !results.is_empty() || !ctx.config.omit_zero_mutation_spells {
+                mutations.entry(target.name.clone())
                     .or_default().value_mut()
                     .insert(spell.name.clone(), results);
             }
         }
         println!("completed {}", spell.name);
-    });
+        Ok(())
+    })?;
+
+    let words = names
+        .iter()
+        .flat_map(|name| name.split(' '))
+        .filter(|word| !word.is_empty())
+        .collect::<HashSet<_>>();
+    // words on their own would add search levels and splits no spell produced to the diagnostics
+    let spell_diagnostics = ctx
+        .targets
+        .iter_mut()
+        .map(|target| mem::take(&mut target.diagnostics))
+        .collect_vec();
+    let word_mutations: DashMap<Arc<str>, SpellMutations> = DashMap::new();
+    words.par_iter().try_for_each(|word| {
+        ctx.mutate(word, ctx.config.mutation_depth)?;
+        for target in &ctx.targets {
+            if let Some(results) = target.take_mutations(word) {
+                word_mutations.entry(target.name.clone())
+                    .or_default().value_mut()
+                    .insert(word.to_string(), results);
+            }
+        }
+        Ok(())
+    })?;
+    for (target, diagnostics) in ctx.targets.iter_mut().zip(spell_diagnostics) {
+        target.diagnostics = diagnostics;
+    }
     let mutation_end_time = Instant::now();
 
     let mut output = PathBuf::from_str(&ctx.config.output_dir).unwrap();
     for target in &mut ctx.targets {
-        println!("\n\nDiagnostics for {}:", target.spellchecker.name());
+        println!("\n\nDiagnostics for {}:", target.name);
         println!("{}", target.diagnostics.stringify(&ctx.config, false));
-        output.push(target.spellchecker.name());
-        fs::create_dir(&output);
+        output.push(&*target.name);
+        create_dir(&output)?;
 
         output.push(DIAGNOSTICS_FILE);
-        fs::write(&output, target.diagnostics.stringify(&ctx.config, true))
-            .expect("failed to write diagnostics");
+        write_file(&output, target.diagnostics.stringify(&ctx.config, true))?;
         output.pop();
         
         output.push(MUTATED_SPELLS_JSON);
-        fs::write(&output, serde_json::to_string(
-            mutations.get(target.spellchecker.name()).unwrap().value()
-        ).unwrap()).expect("failed to write output");
+        // spells without mutations are kept as null, the json equivalent of "no mutations"
+        let spell_mutations = mutations.get(&target.name).unwrap();
+        let spell_mutations = spell_mutations
+            .iter()
+            .map(|(spell, results)| (spell, (!results.is_empty()).then_some(results)))
+            .collect::<HashMap<_, _>>();
+        write_file(&output, serde_json::to_string(&spell_mutations).unwrap())?;
+        output.pop();
+
+        output.push(MUTATED_WORDS_FILE);
+        let target_word_mutations = word_mutations
+            .remove(&target.name)
+            .map(|(_, it)| it)
+            .unwrap_or_default();
+        write_file(&output, format_word_mutations(target_word_mutations))?;
         output.pop();
 
+        if ctx.config.spoonerisms.is_some() {
+            let target_spoonerisms = spoonerisms
+                .remove(&target.name)
+                .map(|(_, it)| it)
+                .unwrap_or_default();
+            output.push(SPOONERISMS_JSON);
+            write_file(&output, serde_json::to_string(&target_spoonerisms).unwrap())?;
+            output.pop();
+
+            output.push(SPOONERISMS_FILE);
+            write_file(&output, format_spoonerisms(&spells, target_spoonerisms))?;
+            output.pop();
+        }
+
         output.pop();
     }
 
-    format_mutations(spells, mutations, ctx.config);
+    format_mutations(spells, mutations, ctx.config)?;
     let output_end_time = Instant::now();
 
     let dict_init_duration = spell_checker_init_end_time.duration_since(start_time);
@@ -98,19 +228,18 @@ fn main() {
              format_duration(mutation_duration),
              format_duration(output_duration)
     );
+    Ok(())
 }
 
-fn parse_files() -> (MutationConfig, Vec<Spell>) {
-    let config: MutationConfig =
-        serde_json::from_str(&fs::read_to_string("./config.json").expect("failed to load config"))
-            .expect("failed to parse config");
+fn parse_config(cli: &Cli) -> Result<MutationConfig> {
+    read_config(&cli.config)
+}
 
-    let mut spells: Vec<Spell> = serde_json::from_str(
-        &fs::read_to_string(&config.input_file).expect("failed to load spells"),
-    )
-    .expect("failed to parse spells");
+fn parse_spells(config: &MutationConfig, cli: &Cli) -> Result<Vec<Spell>> {
+    let mut spells: Vec<Spell> = read_json(&config.input_file)?;
 
+    spells.retain(|spell| cli.includes_spell(spell));
     spells.sort_unstable();
 
-    (config, spells)
+    Ok(spells)
 }
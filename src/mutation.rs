@@ -1,189 +1,861 @@
 mod add_char;
 mod change_char;
+mod homophone;
 mod mutate_string;
+mod phonetic;
 mod remove_char;
+mod spoonerism;
+mod swap_char;
+mod word_edit;
 
+use crate::blocklist::{Blocklist, BLOCKED};
 use crate::diagnostics::Diagnostics;
+use crate::error::{read_json, Error, Result};
+use crate::index::{levenshtein, same_letter, SpellIndex};
+use crate::keyboard::Keyboard;
+use crate::mutation::homophone::Homophones;
 use crate::mutation::mutate_string::MutateStringIter;
-use crate::spellchecking::{CheckResult, SpellChecker};
+use crate::mutation::phonetic::PhoneticIndex;
+use crate::mutation::spoonerism::spoonerisms;
+use crate::mutation::word_edit::word_edits;
+use crate::spellchecking::trie::{min_edits, WordTrie};
+use crate::spellchecking::{build_spell_checker, CheckResult, SpellChecker};
 use dashmap::DashMap;
 use itertools::Itertools;
-use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
-use std::mem::MaybeUninit;
 use std::rc::Rc;
-use std::{fs, mem};
+use std::sync::Arc;
 use std::cmp::min;
 use std::collections::hash_map::Entry;
-use std::sync::Arc;
-use types::{MutationConfig, Overrides};
+use std::fmt::Display;
+use std::hash::{BuildHasher, Hasher};
+use std::time::{Duration, Instant};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use types::{MutationConfig, MutationOperator, OperatorConfig, Overrides, Spell, SpoonerismConfig};
 
 pub struct MutationContext {
     pub overrides: Overrides,
     pub config: MutationConfig,
     pub targets: Vec<MutationTarget>,
+    pub dictionary: Option<WordTrie>,
+    pub blocklist: Blocklist,
+    pub keyboard: Option<Keyboard>,
+    phonetic: Option<PhoneticIndex>,
+    homophones: Option<Homophones>,
 }
 
 impl MutationContext {
-    pub fn new(config: MutationConfig, targets: Vec<MutationTarget>) -> Self {
-        let overrides: Overrides = serde_json::from_str(
-            &fs::read_to_string(&config.overrides_file).expect("failed to load overrides"),
-        )
-        .expect("failed to parse overrides");
+    pub fn new(config: MutationConfig, targets: Vec<MutationTarget>) -> Result<Self> {
+        // every target writes its results to a directory named after it
+        if let Some(target) = targets.iter().duplicates_by(|target| target.name.clone()).next() {
+            return Err(Error::DuplicateSpellChecker(target.name.to_string()));
+        }
+        let overrides: Overrides = read_json(&config.overrides_file)?;
+        let blocklist = Blocklist::new(&config.blocklist)?;
+        let keyboard = config.keyboard.as_ref().map(Keyboard::new);
+        let phonetic = config.phonetic.as_ref().map(PhoneticIndex::new).transpose()?;
+        let homophones = config
+            .operators
+            .iter()
+            .any(|it| it.operator == MutationOperator::Homophone)
+            .then(|| Homophones::new(&config.homophones.file))
+            .transpose()?;
 
-        Self {
+        // pruning is only possible when every spell checker knows all the words it accepts
+        let dictionary = targets.iter().try_fold(WordTrie::new(), |mut acc, target| {
+            acc.extend(target.spellchecker.words()?);
+            Some(acc)
+        });
+
+        Ok(Self {
             config,
             overrides,
             targets,
-        }
+            dictionary,
+            blocklist,
+            keyboard,
+            phonetic,
+            homophones,
+        })
     }
 
-    pub fn submit(&self, original: &str, processed: &str, mutation: &str, depth: usize) {
-        if processed == mutation {
-            return;
+    pub fn from_config(config: MutationConfig) -> Result<Self> {
+        let targets = config
+            .spell_checkers
+            .iter()
+            .map(|entry| {
+                let target = MutationTarget::new(build_spell_checker(&entry.checker)?);
+                Ok(match &entry.label {
+                    Some(label) => target.labelled(label),
+                    None => target,
+                })
+            })
+            .collect::<Result<_>>()?;
+        Self::new(config, targets)
+    }
+
+    pub fn add_target(&mut self, target: MutationTarget) -> Result<()> {
+        if self.targets.iter().any(|it| it.name == target.name) {
+            return Err(Error::DuplicateSpellChecker(target.name.to_string()));
         }
+        self.dictionary = match (self.dictionary.take(), target.spellchecker.words()) {
+            (Some(mut dictionary), Some(words)) => {
+                dictionary.extend(words);
+                Some(dictionary)
+            }
+            _ => None,
+        };
+        self.targets.push(target);
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn submit(
+        &self,
+        original: &str,
+        name: &str,
+        processed: &str,
+        mutation: &str,
+        depth: usize,
+        path: &[Edit],
+        kind: MutationKind,
+    ) {
         for target in &self.targets {
-            target.submit(original, processed, mutation, depth)
+            target.submit(
+                original,
+                name,
+                processed,
+                mutation,
+                depth,
+                path,
+                kind,
+                &self.blocklist,
+                self.keyboard.as_ref(),
+            )
         }
     }
 
-    pub fn log_initial_word(&self, word: &str) {
+    pub fn log_initial_words(&self, string: &str) -> Result<()> {
+        for word in process_split(string, &self.overrides)? {
+            for target in &self.targets {
+                target.diagnostics.log_initial_word(word.to_string());
+            }
+        }
+        Ok(())
+    }
+
+    pub fn log_search_level(
+        &self,
+        depth: usize,
+        checked: usize,
+        duplicates: usize,
+        pruned: usize,
+        time: Duration,
+    ) {
         for target in &self.targets {
-            target.diagnostics.log_initial_word(word.to_string());
+            target.diagnostics.log_search_level(depth, checked, duplicates, pruned, time);
+        }
+    }
+
+    fn is_reachable(
+        &self,
+        processed: &str,
+        mutation: &str,
+        edits: usize,
+        drops: usize,
+        originals: &WordTrie,
+    ) -> bool {
+        let Some(dictionary) = &self.dictionary else {
+            return true;
+        };
+        let tries = [dictionary, originals];
+        let mut costs = vec![];
+        for (original, split) in processed.split('$').zip_eq(mutation.split('$')) {
+            if original == split {
+                continue;
+            }
+            let cost = split.split(' ').try_fold(0, |cost, word| {
+                Some(cost + min_edits(&tries, word.as_bytes(), edits - cost)?)
+            });
+            costs.push(cost.unwrap_or(usize::MAX));
+        }
+        // dropping a word costs a single edit, however far it is from a real one
+        costs.sort_unstable_by(|first, second| second.cmp(first));
+        let dropped = costs.iter().take(drops).take_while(|cost| **cost > 1).count();
+        costs[dropped..]
+            .iter()
+            .try_fold(dropped, |needed, cost| needed.checked_add(*cost))
+            .is_some_and(|needed| needed <= edits)
+    }
+
+    pub fn mutate(&self, string: &str, depth: usize) -> Result<()> {
+        mutate_string(string, depth, None, self)?;
+        // whole words sounding alike or trading sounds are no typos
+        if self.keyboard.as_ref().is_some_and(Keyboard::restricted) {
+            return Ok(());
         }
+        if let Some(phonetic) = &self.phonetic {
+            mutate_phonetic(string, depth, phonetic, self)?;
+        }
+        if let Some(config) = &self.config.spoonerisms {
+            mutate_spoonerisms(string, config, self)?;
+        }
+        Ok(())
+    }
+
+    pub fn mutate_towards(&self, string: &str, depth: usize, goal: &str) -> Result<()> {
+        mutate_string(string, depth, Some(goal), self)
     }
 
-    pub fn mutate(&self, string: &str, depth: usize) {
-        mutate_string(string, depth, self);
+    pub fn mutate_phrase(&self, phrase: &str) -> Result<Vec<PhraseMutations>> {
+        let name = normalize_name(phrase)?;
+        self.mutate(&name, self.config.mutation_depth)?;
+        Ok(self
+            .targets
+            .iter()
+            .map(|target| {
+                let mutations = target.take_mutations(&name).unwrap_or_default();
+                let spoonerisms = target.take_spoonerisms(&name).unwrap_or_default();
+                PhraseMutations {
+                    checker: target.name.clone(),
+                    mutations,
+                    spoonerisms,
+                }
+            })
+            .collect())
     }
+
+    pub fn processed_name(&self, name: &str) -> Result<String> {
+        let name = normalize_name(name)?;
+        Ok(process_split(&name, &self.overrides)?.join(" "))
+    }
+
+    pub fn reverse_search<'a>(
+        &self,
+        spells: &'a [Spell],
+        target: &str,
+        depth: usize,
+    ) -> Result<Vec<ReverseMatch<'a>>> {
+        let target = self.processed_name(target)?;
+        let names = spells
+            .iter()
+            .enumerate()
+            .map(|(idx, spell)| Ok((idx, self.processed_name(&spell.name)?)))
+            .collect::<Result<Vec<_>>>()?;
+        let index = SpellIndex::new(names);
+        // a swap changes two letters with a single edit
+        let budget = OperatorBudget::new(&self.config.operators);
+        let swaps = budget.uses(MutationOperator::Swap);
+        let max_distance = match budget.moves_words() {
+            // a single word edit can move any number of letters
+            true => usize::MAX,
+            false => {
+                // and a homophone swap can change every letter of a word
+                let homophone_swaps = match self.config.homophones.free {
+                    true => budget.uses(MutationOperator::Homophone),
+                    false => min(depth, budget.uses(MutationOperator::Homophone)),
+                };
+                let longest = self.homophones.as_ref().map_or(0, |it| it.longest);
+                (depth + min(depth, swaps)).saturating_add(homophone_swaps.saturating_mul(longest))
+            }
+        };
+
+        let mut matches = index
+            .find(&target, max_distance)
+            .into_par_iter()
+            .map(|(_, spell_ids)| {
+                let name = normalize_name(&spells[spell_ids[0]].name)?;
+                self.mutate_towards(&name, depth, &target)?;
+                let results = self
+                    .targets
+                    .iter()
+                    .filter_map(|target_checker| {
+                        let result = target_checker.take_mutations(&name)?.remove(&target)?;
+                        Some((target_checker.name.clone(), result))
+                    })
+                    .collect_vec();
+                if results.is_empty() {
+                    return Ok(vec![]);
+                }
+                Ok(spell_ids
+                    .iter()
+                    .map(|idx| ReverseMatch {
+                        spell: &spells[*idx],
+                        results: results.clone(),
+                    })
+                    .collect_vec())
+            })
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .flatten()
+            .collect_vec();
+        matches.sort_unstable_by_key(|it| {
+            (it.results.iter().map(|(_, result)| result.depth).min(), it.spell)
+        });
+        Ok(matches)
+    }
+}
+
+pub struct PhraseMutations {
+    pub checker: Arc<str>,
+    pub mutations: HashMap<String, MutationResult>,
+    pub spoonerisms: HashMap<String, MutationResult>,
+}
+
+pub struct ReverseMatch<'a> {
+    pub spell: &'a Spell,
+    pub results: Vec<(Arc<str>, MutationResult)>,
 }
 
 pub struct MutationTarget {
+    pub name: Arc<str>,
     pub spellchecker: Box<dyn SpellChecker>,
     pub diagnostics: Diagnostics,
-    pub results: DashMap<String, HashMap<String, (CheckResult, usize)>>,
+    pub results: DashMap<String, HashMap<String, MutationResult>>,
+    // kept apart from the results, they'd take over letter edits that reach the same string
+    pub spoonerisms: DashMap<String, HashMap<String, MutationResult>>,
 }
 
 impl MutationTarget {
     pub fn new(spellchecker: Box<dyn SpellChecker>) -> Self {
         Self {
+            name: Arc::from(spellchecker.name()),
             spellchecker,
             diagnostics: Diagnostics::new(),
             results: DashMap::new(),
+            spoonerisms: DashMap::new(),
         }
     }
 
-    pub fn submit(&self, original: &str, processed: &str, mutation: &str, depth: usize) {
-        let check_result = processed
-            .split('$')
-            .zip_eq(mutation.split('$'))
-            .fold(CheckResult::SUCCESS, |b, (original, mutation)| {
-                self.spellchecker.check_split(original, mutation).worst(b)
-            });
+    pub fn labelled(self, label: &str) -> Self {
+        Self {
+            name: Arc::from(label),
+            ..self
+        }
+    }
 
-        if !check_result.is_fail() {
-            let result = String::new();
+    #[allow(clippy::too_many_arguments)]
+    pub fn submit(
+        &self,
+        original: &str,
+        name: &str,
+        processed: &str,
+        mutation: &str,
+        depth: usize,
+        path: &[Edit],
+        kind: MutationKind,
+        blocklist: &Blocklist,
+        keyboard: Option<&Keyboard>,
+    ) {
+        let check_result = self.spellchecker.check_mutation(name, processed, mutation);
+        if check_result.is_fail() {
+            return;
+        }
+        let Some(check_result) = blocklist.check(processed, mutation, check_result) else {
+            self.diagnostics.log_blocked();
+            return;
+        };
+        if check_result == BLOCKED {
+            self.diagnostics.log_blocked();
+        }
 
-            for (original, split) in processed.split('$').zip(mutation.split('$')) {
-                if split.contains(' ') {
-                    self.diagnostics.log_procedural_split(
-                        original.to_string(),
-                        split.to_string(),
-                        check_result,
-                    )
+        for (original, split) in processed.split('$').zip(mutation.split('$')) {
+            if split.contains(' ') {
+                self.diagnostics.log_procedural_split(
+                    original.to_string(),
+                    split.to_string(),
+                    check_result,
+                )
+            }
+        }
+        // only letter edits are typos
+        let typo = keyboard
+            .filter(|_| kind == MutationKind::Edit)
+            .and_then(|keyboard| keyboard.score(path, mutation));
+        let results = match kind {
+            MutationKind::Spoonerism => &self.spoonerisms,
+            _ => &self.results,
+        };
+        let mut target = results
+            .entry(original.to_string())
+            .or_default();
+        match target.value_mut().entry(mutation.split('$').join(" ")) {
+            Entry::Occupied(mut entry) => {
+                let result = entry.get_mut();
+                // of the paths at the same depth, the likeliest typo explains the mutation best
+                let likelier = depth == result.depth
+                    && typo.zip(result.typo).is_some_and(|(typo, score)| typo < score);
+                if depth < result.depth || likelier {
+                    result.depth = depth;
+                    result.path = path.to_vec();
+                    result.kind = kind;
+                    result.typo = typo;
                 }
             }
-            let mut target = self.results
-                .entry(original.to_string())
-                .or_default();
-            let target = target.value_mut()
-                .entry(mutation.split('$').join(" "))
-                .or_insert((check_result, depth));
-            target.1 = min(target.1, depth);
+            Entry::Vacant(entry) => {
+                entry.insert(MutationResult {
+                    check: check_result,
+                    phrase: self.spellchecker.forms_phrase(name, processed, mutation),
+                    capitalized: self.capitalized(processed, mutation),
+                    depth,
+                    path: path.to_vec(),
+                    kind,
+                    typo,
+                });
+            }
         }
     }
 
-    pub fn take_mutations(&self, original: &str) -> Option<HashMap<String, (CheckResult, usize)>> {
+    // positions of the proper nouns among the words of the mutation
+    fn capitalized(&self, processed: &str, mutation: &str) -> Vec<usize> {
+        processed
+            .split('$')
+            .zip(mutation.split('$'))
+            .flat_map(|(original, split)| split.split(' ').map(move |word| (original, word)))
+            .positions(|(original, word)| self.spellchecker.is_proper_noun(original, word))
+            .collect()
+    }
+
+    pub fn take_mutations(&self, original: &str) -> Option<HashMap<String, MutationResult>> {
         self.results.remove(original).map(|(_, it)| it)
     }
+
+    pub fn take_spoonerisms(&self, original: &str) -> Option<HashMap<String, MutationResult>> {
+        self.spoonerisms.remove(original).map(|(_, it)| it)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Debug)]
+pub struct Edit {
+    pub operator: MutationOperator,
+    pub position: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub old: Option<char>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub new: Option<char>,
+    // the word replaced by a word level edit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub word: Option<String>,
+}
+
+impl Edit {
+    fn undo(&self, chars: &mut Vec<char>) {
+        match self.operator {
+            MutationOperator::Add => {
+                chars.remove(self.position);
+            }
+            MutationOperator::Change => chars[self.position] = self.old.unwrap(),
+            MutationOperator::Remove => chars.insert(self.position, self.old.unwrap()),
+            MutationOperator::Swap => chars.swap(self.position, self.position + 1),
+            MutationOperator::Homophone => {
+                let end = chars[self.position..]
+                    .iter()
+                    .position(|it| *it == ' ')
+                    .map_or(chars.len(), |len| self.position + len);
+                chars.splice(self.position..end, self.word.as_ref().unwrap().chars());
+            }
+            MutationOperator::DropWord => {
+                chars.splice(self.position..self.position, self.word.as_ref().unwrap().chars());
+            }
+            MutationOperator::SwapWords => {
+                let word = self.word.as_ref().unwrap();
+                chars.splice(self.position..self.position + word.len(), word.chars());
+            }
+            MutationOperator::DuplicateWord => {
+                chars.drain(self.position..self.position + self.word.as_ref().unwrap().len());
+            }
+        }
+    }
+}
+
+// how a mutation was found, letter edits are the default and go unlabelled
+#[derive(Serialize, Deserialize, Copy, Clone, Eq, PartialEq, Default, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum MutationKind {
+    #[default]
+    Edit,
+    Phonetic,
+    Homophone,
+    Spoonerism,
+}
+
+impl MutationKind {
+    fn of(path: &[Edit]) -> Self {
+        match path.iter().any(|edit| edit.operator == MutationOperator::Homophone) {
+            true => MutationKind::Homophone,
+            false => MutationKind::Edit,
+        }
+    }
+
+    fn is_edit(&self) -> bool {
+        *self == MutationKind::Edit
+    }
+}
+
+impl Display for MutationKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MutationKind::Edit => Ok(()),
+            MutationKind::Phonetic => write!(f, "[phonetic] "),
+            MutationKind::Homophone => write!(f, "[homophone] "),
+            MutationKind::Spoonerism => write!(f, "[spoonerism] "),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct MutationResult {
+    pub check: CheckResult,
+    // phrases are listed ahead of every other check result
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub phrase: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub capitalized: Vec<usize>,
+    pub depth: usize,
+    pub path: Vec<Edit>,
+    #[serde(default, skip_serializing_if = "MutationKind::is_edit")]
+    pub kind: MutationKind,
+    // how far the keys typed are from the ones meant, when scoring typos
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub typo: Option<usize>,
+}
+
+impl MutationResult {
+    // what results are listed by, phrases first and then the best check results
+    pub fn rank(&self) -> (bool, CheckResult) {
+        (!self.phrase, self.check)
+    }
+
+    pub fn steps(&self, mutation: &str) -> Vec<String> {
+        edit_steps(&self.path, mutation)
+    }
+}
+
+// the string before the first edit of the path and after each of them
+pub fn edit_steps(path: &[Edit], mutation: &str) -> Vec<String> {
+    let mut chars = mutation.chars().collect_vec();
+    let mut steps = vec![mutation.to_string()];
+    for edit in path.iter().rev() {
+        edit.undo(&mut chars);
+        steps.push(chars.iter().collect());
+    }
+    steps.reverse();
+    steps
 }
 
-fn mutate_string(string: &str, depth: usize, ctx: &MutationContext) {
-    let words = process_split(string.as_bytes(), &ctx.overrides);
-    for word in &words {
-        ctx.log_initial_word(str::from_utf8(word).unwrap());
+pub fn normalize_name(name: &str) -> Result<String> {
+    // anything outside ascii would otherwise quietly turn into a word break
+    if let Some(character) = name.chars().find(|it| !it.is_ascii()) {
+        return Err(Error::UnsupportedCharacter {
+            name: name.to_string(),
+            character,
+        });
     }
+    let name: String = name
+        .chars()
+        .filter(|c| *c != '\'')
+        .map(|c| c.to_ascii_lowercase())
+        .map(|c| if !c.is_ascii_alphabetic() { ' ' } else { c })
+        .collect();
+    // empty words would throw off the word boundaries of mutations
+    Ok(name.split_whitespace().join(" "))
+}
+
+fn mutate_string(
+    string: &str,
+    depth: usize,
+    goal: Option<&str>,
+    ctx: &MutationContext,
+) -> Result<()> {
+    let words = process_split(string, &ctx.overrides)?;
 
     let mut chars = Vec::new();
     for word in words {
         if !chars.is_empty() {
             chars.push(b'$');
         }
-        chars.extend(word);
+        chars.extend(word.as_bytes());
     }
     let processed_string = String::from_utf8(chars.clone()).unwrap();
+    let originals = WordTrie::from_words(processed_string.split('$'));
 
-    let mut mutation_state = vec![];
-    for _ in 0..depth {
-        mutation_state.push(RefCell::new(None))
-    }
-    mutation_state[0] = RefCell::new(Some(MutateStringIter::new(&chars)));
-    loop {
-        let (depth_idx, mutation_iter) = mutation_state
-            .iter()
-            .find_position(|mutations| mutations.borrow().is_none())
-            .unwrap_or_else(|| (0, &mutation_state[0]));
+    let is_goal = |mutation: &[u8]| {
+        goal.is_none_or(|goal| {
+            goal.len() == mutation.len()
+                && goal.bytes().zip(mutation).all(|(first, second)| same_letter(first, *second))
+        })
+    };
 
-        if mutation_iter.borrow().is_none() {
-            if depth_idx == 0 {
-                break;
+    // a string reached with more of the limited operators left can reach more from there, so
+    // the same string only counts as visited with the same budget
+    let budgets = rapidhash::fast::RandomState::new();
+    let mut visited = HashSet::with_hasher(rapidhash::fast::RandomState::new());
+    let budget = OperatorBudget::new(&ctx.config.operators);
+    visited.insert((fingerprint(&chars), budget.key(None, &budgets)));
+    // letter edits carry on from the homophone swaps, which join the search at the level they
+    // cost
+    let mut seeds = (0..depth).map(|_| vec![]).collect_vec();
+    if let Some(homophones) = &ctx.homophones {
+        let cost = !ctx.config.homophones.free as usize;
+        let max_swaps = budget.uses(MutationOperator::Homophone);
+        for (swap, path) in homophones.swaps(&processed_string, max_swaps) {
+            let level = path.len() * cost;
+            let budget = path
+                .iter()
+                .fold(budget.clone(), |budget, edit| budget.spend(edit.operator));
+            let allowed = ctx
+                .keyboard
+                .as_ref()
+                .is_none_or(|it| path.iter().all(|edit| it.allows(edit, swap.as_bytes())));
+            let key = budget.key(None, &budgets);
+            if level > depth || !allowed || !visited.insert((fingerprint(swap.as_bytes()), key)) {
+                continue;
             }
-            let lower_mutation_iter = &mutation_state[depth_idx - 1];
-            let mut lower_mutation_iter = lower_mutation_iter.borrow_mut();
-            if let Some(mutation) = lower_mutation_iter.as_mut().unwrap().next() {
+            if is_goal(swap.as_bytes()) {
                 ctx.submit(
                     string,
                     &processed_string,
-                    str::from_utf8(mutation).unwrap(),
-                    depth_idx - 1,
+                    &processed_string,
+                    &swap,
+                    level.saturating_sub(1),
+                    &path,
+                    MutationKind::Homophone,
                 );
-                *mutation_iter.borrow_mut() = Some(MutateStringIter::new(mutation));
-                if depth_idx != depth - 1 {
+            }
+            if level < depth {
+                let aligned = Rc::from(processed_string.as_str());
+                seeds[level].push((Box::from(swap.as_bytes()), aligned, budget, path));
+            }
+        }
+    }
+
+    let aligned = Rc::from(processed_string.as_str());
+    let mut frontier = vec![(chars.into_boxed_slice(), aligned, budget, vec![])];
+    for (depth_idx, mut level_seeds) in seeds.into_iter().enumerate() {
+        let level_start = Instant::now();
+        frontier.append(&mut level_seeds);
+        let mut next_frontier = vec![];
+        let mut checked = 0;
+        let mut duplicates = 0;
+        let mut pruned = 0;
+        for (chars, aligned, budget, mut path) in frontier {
+            let kind = MutationKind::of(&path);
+            let mut mutation_iter = MutateStringIter::new(&chars, budget.enabled());
+            let word_edits = word_edits(&chars, &aligned, budget.enabled());
+            let mut word_edits = word_edits.iter();
+            loop {
+                let (edit, mutation, aligned) = match mutation_iter.next() {
+                    Some((edit, mutation)) => (edit, mutation, &aligned),
+                    None => match word_edits.next() {
+                        Some((edit, mutation, aligned)) => (edit.clone(), &mutation[..], aligned),
+                        None => break,
+                    },
+                };
+                // a restricted keyboard only lets through letters a finger could slip to
+                let allowed = ctx.keyboard.as_ref().is_none_or(|it| it.allows(&edit, mutation));
+                if !allowed {
                     continue;
                 }
-            } else {
-                *lower_mutation_iter = None;
-                continue;
+                // edits that undo each other get back to the original with less budget left
+                let key = budget.key(Some(edit.operator), &budgets);
+                if *mutation == *processed_string.as_bytes()
+                    || !visited.insert((fingerprint(mutation), key))
+                {
+                    duplicates += 1;
+                    continue;
+                }
+                checked += 1;
+
+                let operator = edit.operator;
+                path.push(edit);
+                if is_goal(mutation) {
+                    ctx.submit(
+                        string,
+                        &processed_string,
+                        aligned,
+                        str::from_utf8(mutation).unwrap(),
+                        depth_idx,
+                        &path,
+                        kind,
+                    );
+                }
+                if depth_idx + 1 < depth {
+                    let budget = budget.spend(operator);
+                    // a swap can fix two letters with one edit
+                    let remaining = depth - depth_idx - 1;
+                    let edits = remaining + min(remaining, budget.uses(MutationOperator::Swap));
+                    let drops = min(remaining, budget.uses(MutationOperator::DropWord));
+                    let mutation_str = str::from_utf8(mutation).unwrap();
+                    let reachable = match goal {
+                        // moving words around can get any number of letters closer to the goal
+                        Some(_) if budget.moves_words() => true,
+                        // homophone swaps only ever seed the search, none are left to make here
+                        Some(goal) => levenshtein(mutation, goal.as_bytes()) <= edits,
+                        None => ctx.is_reachable(aligned, mutation_str, edits, drops, &originals),
+                    };
+                    if reachable {
+                        let next = (Box::from(mutation), aligned.clone(), budget, path.clone());
+                        next_frontier.push(next);
+                    } else {
+                        pruned += 1;
+                    }
+                }
+                path.pop();
             }
         }
+        ctx.log_search_level(depth_idx, checked, duplicates, pruned, level_start.elapsed());
+        frontier = next_frontier;
+    }
+    Ok(())
+}
+
+// swaps whole words for ones that sound alike, each swapped word costs a level and every sound
+// it differs by costs another
+fn mutate_phonetic(
+    string: &str,
+    depth: usize,
+    phonetic: &PhoneticIndex,
+    ctx: &MutationContext,
+) -> Result<()> {
+    let words = process_split(string, &ctx.overrides)?;
+    let processed_string = words.join("$");
+
+    let mut mutations: Vec<(Vec<&str>, usize)> = vec![(vec![], 0)];
+    for word in words {
+        let options = phonetic
+            .sounds_like(word)
+            .into_iter()
+            .map(|(found, distance)| (found, distance + 1))
+            .chain([(word, 0)])
+            .collect_vec();
+        mutations = mutations
+            .iter()
+            .cartesian_product(&options)
+            .filter(|((_, cost), (_, word_cost))| cost + word_cost <= depth)
+            .map(|((mutation, cost), (found, word_cost))| {
+                let mut mutation = mutation.clone();
+                mutation.push(*found);
+                (mutation, cost + word_cost)
+            })
+            .collect();
+    }
 
-        let mut mutation_iter_option = mutation_iter.borrow_mut();
-        let mut mutation_iter = mutation_iter_option.as_mut().unwrap();
-        while let Some(mutation) = mutation_iter.next() {
+    for (mutation, cost) in mutations {
+        if cost > 0 {
+            let mutation = mutation.join("$");
             ctx.submit(
                 string,
                 &processed_string,
-                str::from_utf8(mutation).unwrap(),
-                depth_idx,
-            )
+                &processed_string,
+                &mutation,
+                cost - 1,
+                &[],
+                MutationKind::Phonetic,
+            );
         }
-        *mutation_iter_option = None
     }
+    Ok(())
 }
 
-fn process_split<'a>(string: &'a [u8], overrides: &Overrides) -> Vec<&'a [u8]> {
-    for letter in string {
-        if !(letter.is_ascii_lowercase() || *letter == b' ') {
-            panic!(
-                "unknown character spotted in \"{}\"",
-                str::from_utf8(string).unwrap()
-            )
+// spoonerisms aren't edits of the spell name, they get a section of their own rather than a level
+fn mutate_spoonerisms(string: &str, config: &SpoonerismConfig, ctx: &MutationContext) -> Result<()> {
+    let words = process_split(string, &ctx.overrides)?;
+    let processed_string = words.join("$");
+    for spoonerism in spoonerisms(&words, config.vowels) {
+        let kind = MutationKind::Spoonerism;
+        ctx.submit(string, &processed_string, &processed_string, &spoonerism, 0, &[], kind);
+    }
+    Ok(())
+}
+
+// visited strings are tracked by a 128 bit hash, allocating every unique string costs more than
+// the duplicate checks it saves
+fn fingerprint(chars: &[u8]) -> u128 {
+    static SECRETS: rapidhash::v3::RapidSecrets = rapidhash::v3::RapidSecrets::seed(0x5eed);
+    (rapidhash::v3::rapidhash_v3(chars) as u128) << 64
+        | rapidhash::v3::rapidhash_v3_seeded(chars, &SECRETS) as u128
+}
+
+#[derive(Clone)]
+struct OperatorBudget {
+    remaining: Vec<(MutationOperator, usize)>,
+}
+
+impl OperatorBudget {
+    fn new(operators: &[OperatorConfig]) -> Self {
+        let mut remaining = operators
+            .iter()
+            .map(|it| (it.operator, it.max_uses.unwrap_or(usize::MAX)))
+            .collect_vec();
+        remaining.sort_unstable();
+        remaining.dedup_by_key(|(operator, _)| *operator);
+        Self { remaining }
+    }
+
+    fn enabled(&self) -> impl Iterator<Item = MutationOperator> + '_ {
+        self.remaining
+            .iter()
+            .filter(|(_, uses)| *uses > 0)
+            .map(|(operator, _)| *operator)
+    }
+
+    // whether any operator that moves words between positions is left
+    fn moves_words(&self) -> bool {
+        [MutationOperator::DropWord, MutationOperator::SwapWords, MutationOperator::DuplicateWord]
+            .into_iter()
+            .any(|operator| self.uses(operator) > 0)
+    }
+
+    fn uses(&self, operator: MutationOperator) -> usize {
+        self.remaining
+            .iter()
+            .find(|(it, _)| *it == operator)
+            .map_or(0, |(_, uses)| *uses)
+    }
+
+    // identifies the uses left, after spending the operator if there is one
+    fn key(&self, spending: Option<MutationOperator>, hasher: &impl BuildHasher) -> u64 {
+        let mut state = hasher.build_hasher();
+        for (operator, uses) in &self.remaining {
+            match Some(*operator) == spending {
+                true => state.write_usize(spent(*uses)),
+                false => state.write_usize(*uses),
+            }
+        }
+        state.finish()
+    }
+
+    fn spend(&self, operator: MutationOperator) -> Self {
+        let mut budget = self.clone();
+        for (it, uses) in &mut budget.remaining {
+            if *it == operator {
+                *uses = spent(*uses);
+            }
         }
+        budget
+    }
+}
+
+// unlimited operators stay unlimited however often they're used
+fn spent(uses: usize) -> usize {
+    match uses {
+        usize::MAX => uses,
+        _ => uses.saturating_sub(1),
+    }
+}
+
+fn process_split<'a>(string: &'a str, overrides: &Overrides) -> Result<Vec<&'a str>> {
+    if let Some(character) = string.chars().find(|it| !(it.is_ascii_lowercase() || *it == ' ')) {
+        return Err(Error::UnsupportedCharacter {
+            name: string.to_string(),
+            character,
+        });
     }
 
     let mut result = vec![];
-    for mut word in string.split(|it| *it == b' ') {
-        while let Some(index) = overrides.allow_split.get(str::from_utf8(word).unwrap()) {
+    for mut word in string.split(' ') {
+        while let Some(index) = overrides.allow_split.get(word) {
             let (first, second) = word.split_at(*index);
             result.push(first);
             word = second;
@@ -191,5 +863,112 @@ fn process_split<'a>(string: &'a [u8], overrides: &Overrides) -> Vec<&'a [u8]> {
         result.push(word)
     }
 
-    result
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LETTER_OPERATORS: [MutationOperator; 4] = [
+        MutationOperator::Add,
+        MutationOperator::Change,
+        MutationOperator::Remove,
+        MutationOperator::Swap,
+    ];
+    const WORD_OPERATORS: [MutationOperator; 3] = [
+        MutationOperator::DropWord,
+        MutationOperator::SwapWords,
+        MutationOperator::DuplicateWord,
+    ];
+
+    // every mutation one letter edit away, keyed the way results are
+    fn letter_edits(chars: &[u8]) -> Vec<(Edit, String)> {
+        let mut edits = vec![];
+        let mut iter = MutateStringIter::new(chars, LETTER_OPERATORS);
+        while let Some((edit, mutation)) = iter.next() {
+            edits.push((edit, str::from_utf8(mutation).unwrap().to_string()));
+        }
+        edits
+    }
+
+    fn key(mutation: &str) -> String {
+        mutation.replace('$', " ")
+    }
+
+    #[test]
+    fn names_normalize_to_lowercase_words() {
+        assert_eq!(normalize_name("Tasha's Hideous Laughter").unwrap(), "tashas hideous laughter");
+        assert_eq!(normalize_name("Melf's  Acid-Arrow").unwrap(), "melfs acid arrow");
+        assert_eq!(normalize_name(" Wish ").unwrap(), "wish");
+    }
+
+    #[test]
+    fn names_outside_ascii_are_rejected() {
+        let error = normalize_name("Naïve Ñ").unwrap_err();
+        assert!(matches!(error, Error::UnsupportedCharacter { character: 'ï', .. }));
+    }
+
+    #[test]
+    fn letter_edits_undo_to_the_original() {
+        let edits = letter_edits(b"fire$bolt");
+        for operator in LETTER_OPERATORS {
+            assert!(edits.iter().any(|(edit, _)| edit.operator == operator), "{operator:?}");
+        }
+        for (edit, mutation) in edits {
+            let steps = edit_steps(std::slice::from_ref(&edit), &key(&mutation));
+            assert_eq!(steps, vec!["fire bolt".to_string(), key(&mutation)], "{edit:?}");
+        }
+    }
+
+    #[test]
+    fn letter_edit_paths_undo_step_by_step() {
+        for (first, first_mutation) in letter_edits(b"wish") {
+            for (second, second_mutation) in letter_edits(first_mutation.as_bytes()) {
+                let steps = edit_steps(&[first.clone(), second], &second_mutation);
+                assert_eq!(steps, vec!["wish", first_mutation.as_str(), &second_mutation]);
+            }
+        }
+    }
+
+    #[test]
+    fn word_edits_undo_to_the_original() {
+        let edits = word_edits(b"fire$bolt$wish", "fire$bolt$wish", WORD_OPERATORS);
+        for operator in WORD_OPERATORS {
+            assert!(edits.iter().any(|(edit, _, _)| edit.operator == operator), "{operator:?}");
+        }
+        for (edit, mutation, aligned) in edits {
+            let mutation = key(str::from_utf8(&mutation).unwrap());
+            let steps = edit_steps(std::slice::from_ref(&edit), &mutation);
+            assert_eq!(steps, vec!["fire bolt wish".to_string(), mutation], "{edit:?}");
+            // the original words move along with the mutated ones
+            assert_eq!(aligned.split('$').count(), steps[1].split(' ').count());
+        }
+    }
+
+    #[test]
+    fn letter_edits_after_word_edits_undo_to_the_original() {
+        for (word_edit, word_mutation, _) in word_edits(b"fire$bolt", "fire$bolt", WORD_OPERATORS) {
+            let word_mutation = str::from_utf8(&word_mutation).unwrap().to_string();
+            for (letter_edit, mutation) in letter_edits(word_mutation.as_bytes()) {
+                let path = [word_edit.clone(), letter_edit];
+                let steps = edit_steps(&path, &key(&mutation));
+                assert_eq!(steps[0], "fire bolt", "{path:?}");
+                assert_eq!(steps[1], key(&word_mutation), "{path:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn homophone_swaps_undo_to_the_original() {
+        let homophones = Homophones::new("assets/homophones.txt").unwrap();
+        let swaps = homophones.swaps("sole$knight$rain", 2);
+        assert!(swaps.iter().any(|(swap, _)| swap == "soul$night$rain"));
+        assert!(swaps.iter().all(|(swap, _)| swap != "soul$night$reign"));
+        for (swap, path) in swaps {
+            let steps = edit_steps(&path, &key(&swap));
+            assert_eq!(steps[0], "sole knight rain", "{path:?}");
+            assert_eq!(steps.len(), path.len() + 1);
+        }
+    }
 }
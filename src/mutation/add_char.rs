@@ -1,6 +1,5 @@
-use std::cell::RefMut;
-use std::mem;
-use std::ptr::slice_from_raw_parts;
+use crate::mutation::Edit;
+use types::MutationOperator;
 
 pub(super) struct AddCharIter {
     chars: Box<[u8]>,
@@ -49,6 +48,16 @@ impl AddCharIter {
         &self.chars
     }
 
+    pub(super) fn edit(&self) -> Edit {
+        Edit {
+            operator: MutationOperator::Add,
+            position: self.idx,
+            old: None,
+            new: Some(self.chars[self.idx] as char),
+            word: None,
+        }
+    }
+
     pub(super) fn finish(mut self) -> Box<[u8]> {
         if self.idx < self.chars.len() {
             self.chars.copy_within(self.idx + 1.., self.idx);
@@ -1,5 +1,6 @@
 use std::mem;
-use std::mem::MaybeUninit;
+use crate::mutation::Edit;
+use types::MutationOperator;
 
 pub(super) struct ChangeCharIter {
     chars: Box<[u8]>,
@@ -50,6 +51,16 @@ impl ChangeCharIter {
         &self.chars[..self.partial_len]
     }
 
+    pub(super) fn edit(&self) -> Edit {
+        Edit {
+            operator: MutationOperator::Change,
+            position: self.idx,
+            old: Some(self.original_letter as char),
+            new: Some(self.chars[self.idx] as char),
+            word: None,
+        }
+    }
+
     pub(super) fn finish(mut self) -> Box<[u8]> {
         if self.idx < self.chars.len() {
             self.chars[self.idx] = self.original_letter;
@@ -0,0 +1,71 @@
+use crate::error::{read_file, Result};
+use crate::mutation::Edit;
+use std::collections::{HashMap, HashSet};
+use types::MutationOperator;
+
+pub struct Homophones {
+    words: HashMap<String, HashSet<String>, rapidhash::fast::RandomState>,
+    // the most letters a single swap can change
+    pub longest: usize,
+}
+
+impl Homophones {
+    pub fn new(path: &str) -> Result<Self> {
+        let mut words: HashMap<_, HashSet<_>, _> =
+            HashMap::with_hasher(rapidhash::fast::RandomState::new());
+        for line in read_file(path)?.lines() {
+            if line.starts_with('#') {
+                continue;
+            }
+            // mutations only ever contain lowercase letters
+            let group = line
+                .split(',')
+                .map(str::trim)
+                .filter(|word| !word.is_empty() && word.bytes().all(|it| it.is_ascii_lowercase()))
+                .collect::<Vec<_>>();
+            for word in &group {
+                let homophones = words.entry(word.to_string()).or_default();
+                homophones.extend(group.iter().filter(|it| *it != word).map(|it| it.to_string()));
+            }
+        }
+        let longest = words.keys().map(String::len).max().unwrap_or(0);
+        Ok(Self { words, longest })
+    }
+
+    // every way to swap up to max_swaps words of the processed string for their homophones,
+    // along with the edits that undo the swaps
+    pub fn swaps(&self, processed: &str, max_swaps: usize) -> Vec<(String, Vec<Edit>)> {
+        let mut swaps = vec![(String::new(), vec![])];
+        for word in processed.split('$') {
+            let homophones = self.words.get(word).into_iter().flatten();
+            let mut next_swaps = vec![];
+            for (string, path) in swaps {
+                let position = if string.is_empty() { 0 } else { string.len() + 1 };
+                let join = |word: &str| {
+                    if string.is_empty() {
+                        word.to_string()
+                    } else {
+                        format!("{string}${word}")
+                    }
+                };
+                if path.len() < max_swaps {
+                    for homophone in homophones.clone() {
+                        let mut path = path.clone();
+                        path.push(Edit {
+                            operator: MutationOperator::Homophone,
+                            position,
+                            old: None,
+                            new: None,
+                            word: Some(word.to_string()),
+                        });
+                        next_swaps.push((join(homophone), path));
+                    }
+                }
+                next_swaps.push((join(word), path));
+            }
+            swaps = next_swaps;
+        }
+        swaps.retain(|(_, path)| !path.is_empty());
+        swaps
+    }
+}
@@ -1,56 +1,101 @@
 use crate::mutation::add_char::AddCharIter;
 use crate::mutation::change_char::ChangeCharIter;
 use crate::mutation::remove_char::RemoveCharIter;
-use itertools::Itertools;
-use std::cell::RefCell;
+use crate::mutation::swap_char::SwapCharIter;
+use crate::mutation::Edit;
 use std::mem;
 use std::mem::MaybeUninit;
-use std::rc::Rc;
+use types::MutationOperator;
 
 pub struct MutateStringIter {
-    add_char: Option<AddCharIter>,
-    change_char: Option<ChangeCharIter>,
-    remove_char: Option<RemoveCharIter>,
+    current: Option<OperatorIter>,
+    operators: Vec<MutationOperator>,
 }
 
 impl MutateStringIter {
-    pub fn new(chars: &[u8]) -> Self {
-        Self {
-            add_char: Some(AddCharIter::new(inc_size(chars))),
-            change_char: None,
-            remove_char: None,
-        }
+    pub fn new(chars: &[u8], operators: impl IntoIterator<Item = MutationOperator>) -> Self {
+        let mut operators = operators
+            .into_iter()
+            .filter(|operator| !operator.is_word_level())
+            .collect::<Vec<_>>();
+        operators.sort_unstable_by(|first, second| second.cmp(first));
+        operators.dedup();
+        let current = operators
+            .pop()
+            .map(|operator| OperatorIter::new(operator, inc_size(chars)));
+        Self { current, operators }
     }
 
-    pub fn next(&mut self) -> Option<&[u8]> {
-        self.add_char = self.add_char.take().and_then(|mut it| {
-            if it.next().is_some() {
-                Some(it)
-            } else {
-                self.change_char = Some(ChangeCharIter::new(it.finish()));
-                None
+    pub fn next(&mut self) -> Option<(Edit, &[u8])> {
+        loop {
+            let iter = self.current.as_mut()?;
+            if iter.next() {
+                break;
             }
-        });
-        if let Some(iter) = &self.add_char {
-            return Some(iter.get());
+            let chars = self.current.take().unwrap().finish();
+            self.current = self
+                .operators
+                .pop()
+                .map(|operator| OperatorIter::new(operator, chars));
         }
+        self.current
+            .as_ref()
+            .map(|iter| (iter.edit(), iter.get()))
+    }
+}
 
-        self.change_char = self.change_char.take().and_then(|mut it| {
-            if it.next().is_some() {
-                Some(it)
-            } else {
-                self.remove_char = Some(RemoveCharIter::new(it.finish()));
-                None
-            }
-        });
-        if let Some(iter) = &self.change_char {
-            return Some(iter.get());
+enum OperatorIter {
+    Add(AddCharIter),
+    Change(ChangeCharIter),
+    Remove(RemoveCharIter),
+    Swap(SwapCharIter),
+}
+
+impl OperatorIter {
+    fn new(operator: MutationOperator, chars: Box<[u8]>) -> Self {
+        match operator {
+            MutationOperator::Add => Self::Add(AddCharIter::new(chars)),
+            MutationOperator::Change => Self::Change(ChangeCharIter::new(chars)),
+            MutationOperator::Remove => Self::Remove(RemoveCharIter::new(chars)),
+            MutationOperator::Swap => Self::Swap(SwapCharIter::new(chars)),
+            operator => unreachable!("{operator:?} edits whole words"),
+        }
+    }
+
+    fn edit(&self) -> Edit {
+        match self {
+            Self::Add(iter) => iter.edit(),
+            Self::Change(iter) => iter.edit(),
+            Self::Remove(iter) => iter.edit(),
+            Self::Swap(iter) => iter.edit(),
+        }
+    }
+
+    fn next(&mut self) -> bool {
+        match self {
+            Self::Add(iter) => iter.next().is_some(),
+            Self::Change(iter) => iter.next().is_some(),
+            Self::Remove(iter) => iter.next().is_some(),
+            Self::Swap(iter) => iter.next().is_some(),
         }
+    }
+
+    fn get(&self) -> &[u8] {
+        match self {
+            Self::Add(iter) => iter.get(),
+            Self::Change(iter) => iter.get(),
+            Self::Remove(iter) => iter.get(),
+            Self::Swap(iter) => iter.get(),
+        }
+    }
 
-        if let Some(iter) = &mut self.remove_char {
-            return iter.next();
+    fn finish(self) -> Box<[u8]> {
+        match self {
+            Self::Add(iter) => iter.finish(),
+            Self::Change(iter) => iter.finish(),
+            Self::Remove(iter) => iter.finish(),
+            Self::Swap(iter) => iter.finish(),
         }
-        None
     }
 }
 
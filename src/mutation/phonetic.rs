@@ -0,0 +1,170 @@
+use crate::error::{read_file, Result};
+use crate::index::{levenshtein, SpellIndex};
+use itertools::Itertools;
+use std::cmp::min;
+use types::PhoneticConfig;
+
+pub struct PhoneticIndex {
+    words: Vec<String>,
+    keys: SpellIndex,
+    max_distance: usize,
+}
+
+impl PhoneticIndex {
+    pub fn new(config: &PhoneticConfig) -> Result<Self> {
+        // inflection lists mark some entries with a trailing '%' or '!'
+        let words = read_file(&config.dictionary)?
+            .lines()
+            .map(|line| line.trim_end_matches(['%', '!']))
+            .filter(|word| !word.is_empty() && word.bytes().all(|it| it.is_ascii_lowercase()))
+            .unique()
+            .map(str::to_string)
+            .collect_vec();
+        let keys = SpellIndex::new(
+            words
+                .iter()
+                .enumerate()
+                .map(|(idx, word)| (idx, phonetic_key(word)))
+                .filter(|(_, key)| !key.is_empty()),
+        );
+        Ok(Self {
+            words,
+            keys,
+            max_distance: config.max_distance,
+        })
+    }
+
+    // words that sound like the given one, along with how many sounds they differ by
+    pub fn sounds_like(&self, word: &str) -> Vec<(&str, usize)> {
+        let key = phonetic_key(word);
+        if key.is_empty() {
+            return vec![];
+        }
+        // changing one of only a couple of sounds gives a different word rather than a pun
+        let max_distance = min(self.max_distance, key.len() / 3);
+        self.keys
+            .find(&key, max_distance)
+            .into_iter()
+            .flat_map(|(found, ids)| {
+                let distance = levenshtein(found.as_bytes(), key.as_bytes());
+                ids.iter().map(move |idx| (self.words[*idx].as_str(), distance))
+            })
+            .filter(|(found, _)| *found != word)
+            .collect()
+    }
+}
+
+// a simplified metaphone that keeps the vowels, dropping them leaves too few sounds to tell the
+// words of a pun apart
+pub fn phonetic_key(word: &str) -> String {
+    let letters = word.as_bytes();
+    let at = |idx: usize| letters.get(idx).copied().unwrap_or(0);
+    let is_vowel = |letter: u8| b"aeiou".contains(&letter);
+
+    // the first letter of these is silent
+    let start = match letters.get(..2) {
+        Some(b"ae" | b"gn" | b"kn" | b"pn" | b"wr") => 1,
+        _ => 0,
+    };
+    let mut key = String::new();
+    for idx in start..letters.len() {
+        let letter = letters[idx];
+        let prev = if idx > start { letters[idx - 1] } else { 0 };
+        let next = at(idx + 1);
+        let after = at(idx + 2);
+        // doubled letters sound like one, except for "cc" as in "accent"
+        if letter == prev && letter != b'c' {
+            continue;
+        }
+        let sound = match letter {
+            // a run of vowels sounds like its first one, and a final 'e' is silent
+            b'a' | b'e' | b'i' | b'o' | b'u' if is_vowel(prev) => "",
+            b'e' if idx > start && next == 0 => "",
+            b'a' | b'e' | b'i' | b'o' | b'u' => &word[idx..=idx],
+            b'b' if prev == b'm' && next == 0 => "",
+            b'c' if next == b'i' && after == b'a' => "X",
+            b'c' if next == b'h' && prev == b's' => "K",
+            b'c' if next == b'h' => "X",
+            b'c' if matches!(next, b'e' | b'i' | b'y') && prev == b's' => "",
+            b'c' if matches!(next, b'e' | b'i' | b'y') => "S",
+            b'c' => "K",
+            b'd' if next == b'g' && matches!(after, b'e' | b'i' | b'y') => "J",
+            b'd' => "T",
+            b'g' if next == b'h' && after != 0 && !is_vowel(after) => "",
+            b'g' if prev == b'd' && matches!(next, b'e' | b'i' | b'y') => "",
+            b'g' if next == b'n' && (after == 0 || &letters[idx + 2..] == b"ed") => "",
+            b'g' if matches!(next, b'e' | b'i' | b'y') => "J",
+            b'g' => "K",
+            b'h' if matches!(prev, b'c' | b'g' | b'p' | b's' | b't' | b'w') => "",
+            b'h' if is_vowel(prev) && !is_vowel(next) => "",
+            b'k' if prev == b'c' => "",
+            b'p' if next == b'h' => "F",
+            b'q' => "K",
+            b's' if next == b'h' || (next == b'i' && matches!(after, b'a' | b'o')) => "X",
+            b't' if next == b'i' && matches!(after, b'a' | b'o') => "X",
+            b't' if next == b'h' => "0",
+            b't' if next == b'c' && after == b'h' => "",
+            b'v' => "F",
+            b'w' if idx == start && next == b'h' => "W",
+            b'w' | b'y' if is_vowel(prev) => "",
+            b'y' if !is_vowel(next) => "I",
+            b'w' if !is_vowel(next) => "",
+            b'x' if idx == start => "S",
+            b'x' => "KS",
+            b'z' => "S",
+            _ => &word[idx..=idx],
+        };
+        key.push_str(sound);
+    }
+    key.make_ascii_uppercase();
+    key
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn homophones_share_a_key() {
+        for (first, second) in [
+            ("knight", "night"),
+            ("phase", "faze"),
+            ("write", "rite"),
+            ("sole", "soul"),
+            ("gnome", "nome"),
+            ("cite", "site"),
+            ("knows", "nose"),
+        ] {
+            assert_eq!(phonetic_key(first), phonetic_key(second), "{first} and {second}");
+        }
+    }
+
+    #[test]
+    fn keys_keep_the_vowels() {
+        assert_eq!(phonetic_key("fire"), "FIR");
+        assert_eq!(phonetic_key("fare"), "FAR");
+        assert_eq!(phonetic_key("bolt"), "BOLT");
+        assert_eq!(phonetic_key("aerial"), "ERIL");
+    }
+
+    #[test]
+    fn letter_combinations_make_one_sound() {
+        assert_eq!(phonetic_key("thumb"), "0UM");
+        assert_eq!(phonetic_key("school"), "SKOL");
+        assert_eq!(phonetic_key("science"), "SINS");
+        assert_eq!(phonetic_key("nation"), "NAXIN");
+        assert_eq!(phonetic_key("light"), "LIT");
+        assert_eq!(phonetic_key("edge"), "EJ");
+        assert_eq!(phonetic_key("accent"), "AKSENT");
+        assert_eq!(phonetic_key("xylophone"), "SILOFON");
+        assert_eq!(phonetic_key("wish"), "WIX");
+    }
+
+    #[test]
+    fn y_and_w_are_only_consonants_before_vowels() {
+        assert_eq!(phonetic_key("yes"), "YES");
+        assert_eq!(phonetic_key("boy"), "BO");
+        assert_eq!(phonetic_key("why"), "WI");
+        assert_eq!(phonetic_key("gym"), "JIM");
+    }
+}
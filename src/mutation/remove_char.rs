@@ -1,7 +1,5 @@
-use std::mem;
-use std::mem::ManuallyDrop;
-use std::ops::{Deref, Index};
-use std::ptr::NonNull;
+use crate::mutation::Edit;
+use types::MutationOperator;
 
 pub(super) struct RemoveCharIter {
     chars: Box<[u8]>,
@@ -40,6 +38,20 @@ impl RemoveCharIter {
         None
     }
 
+    pub(super) fn get(&self) -> &[u8] {
+        &self.chars[1..self.partial_len]
+    }
+
+    pub(super) fn edit(&self) -> Edit {
+        Edit {
+            operator: MutationOperator::Remove,
+            position: self.idx - 1,
+            old: Some(self.chars[0] as char),
+            new: None,
+            word: None,
+        }
+    }
+
     pub(super) fn finish(mut self) -> Box<[u8]> {
         if self.partial_len > 0 && self.idx > 0 {
             let removed_char = self.chars[0];
@@ -0,0 +1,97 @@
+use itertools::Itertools;
+use std::collections::HashSet;
+
+// every spoonerism of the words, joined with '$' like the processed string
+pub(super) fn spoonerisms(words: &[&str], vowels: bool) -> Vec<String> {
+    let mut spoonerisms = HashSet::new();
+    for (first, second) in (0..words.len()).tuple_combinations() {
+        let (first_onset, first_vowels, first_rest) = split_onset(words[first]);
+        let (second_onset, second_vowels, second_rest) = split_onset(words[second]);
+
+        let mut swaps = vec![(
+            format!("{second_onset}{first_vowels}{first_rest}"),
+            format!("{first_onset}{second_vowels}{second_rest}"),
+        )];
+        if vowels {
+            swaps.push((
+                format!("{first_onset}{second_vowels}{first_rest}"),
+                format!("{second_onset}{first_vowels}{second_rest}"),
+            ));
+            swaps.push((
+                format!("{second_onset}{second_vowels}{first_rest}"),
+                format!("{first_onset}{first_vowels}{second_rest}"),
+            ));
+        }
+        for (first_word, second_word) in swaps {
+            // swapping everything up to the end of both words only swaps the words around
+            if first_word == words[second] && second_word == words[first] {
+                continue;
+            }
+            let mut spoonerism = words.to_vec();
+            spoonerism[first] = &first_word;
+            spoonerism[second] = &second_word;
+            if spoonerism != words {
+                spoonerisms.insert(spoonerism.join("$"));
+            }
+        }
+    }
+    spoonerisms.into_iter().collect()
+}
+
+// splits a word into its leading consonants, the vowels after them and the rest
+fn split_onset(word: &str) -> (&str, &str, &str) {
+    let letters = word.as_bytes();
+    // a leading 'y' is a consonant, anywhere else it's a vowel
+    let is_vowel = |idx: usize| b"aeiou".contains(&letters[idx]) || (letters[idx] == b'y' && idx > 0);
+
+    let mut onset = (0..letters.len()).find(|idx| is_vowel(*idx)).unwrap_or(letters.len());
+    // the 'u' after a 'q' belongs with it, as in "quick"
+    if onset > 0 && letters[onset - 1] == b'q' && letters.get(onset) == Some(&b'u') {
+        onset += 1;
+    }
+    let vowels = (onset..letters.len()).find(|idx| !is_vowel(*idx)).unwrap_or(letters.len());
+    (&word[..onset], &word[onset..vowels], &word[vowels..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn onsets_end_at_the_first_vowel() {
+        assert_eq!(split_onset("blade"), ("bl", "a", "de"));
+        assert_eq!(split_onset("booming"), ("b", "oo", "ming"));
+        assert_eq!(split_onset("strength"), ("str", "e", "ngth"));
+        assert_eq!(split_onset("arcane"), ("", "a", "rcane"));
+        assert_eq!(split_onset("psst"), ("psst", "", ""));
+    }
+
+    #[test]
+    fn y_is_only_a_consonant_at_the_start() {
+        assert_eq!(split_onset("yellow"), ("y", "e", "llow"));
+        assert_eq!(split_onset("rhythm"), ("rh", "y", "thm"));
+        assert_eq!(split_onset("eye"), ("", "eye", ""));
+    }
+
+    #[test]
+    fn the_u_after_a_q_stays_in_the_onset() {
+        assert_eq!(split_onset("quick"), ("qu", "i", "ck"));
+        assert_eq!(split_onset("squall"), ("squ", "a", "ll"));
+    }
+
+    #[test]
+    fn spoonerisms_swap_onsets() {
+        let found = spoonerisms(&["booming", "blade"], false);
+        assert_eq!(found, vec!["blooming$bade"]);
+
+        let mut found = spoonerisms(&["fire", "bolt"], true);
+        found.sort_unstable();
+        assert_eq!(found, vec!["bire$folt", "bore$filt", "fore$bilt"]);
+    }
+
+    #[test]
+    fn swapping_whole_words_is_not_a_spoonerism() {
+        assert!(spoonerisms(&["ice", "age"], false).is_empty());
+        assert!(spoonerisms(&["wish"], true).is_empty());
+    }
+}
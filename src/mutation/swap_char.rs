@@ -0,0 +1,64 @@
+use crate::mutation::Edit;
+use std::mem;
+use types::MutationOperator;
+
+pub(super) struct SwapCharIter {
+    chars: Box<[u8]>,
+    partial_len: usize,
+    idx: usize,
+    swapped: bool,
+}
+
+impl SwapCharIter {
+    pub(super) fn new(chars: Box<[u8]>) -> Self {
+        let partial_len = chars.len() - 1;
+        Self {
+            chars,
+            partial_len,
+            idx: 0,
+            swapped: false,
+        }
+    }
+
+    pub(super) fn next(&mut self) -> Option<&[u8]> {
+        self.restore();
+        while self.idx + 1 < self.partial_len {
+            let idx = self.idx;
+            self.idx += 1;
+            let (first, second) = (self.chars[idx], self.chars[idx + 1]);
+            // skip spaces and swaps that don't change anything
+            if !first.is_ascii_alphabetic() || !second.is_ascii_alphabetic() || first == second {
+                continue;
+            }
+            self.chars.swap(idx, idx + 1);
+            self.swapped = true;
+            return Some(&self.chars[..self.partial_len]);
+        }
+        None
+    }
+
+    pub(super) fn get(&self) -> &[u8] {
+        &self.chars[..self.partial_len]
+    }
+
+    pub(super) fn edit(&self) -> Edit {
+        Edit {
+            operator: MutationOperator::Swap,
+            position: self.idx - 1,
+            old: Some(self.chars[self.idx] as char),
+            new: Some(self.chars[self.idx - 1] as char),
+            word: None,
+        }
+    }
+
+    pub(super) fn finish(mut self) -> Box<[u8]> {
+        self.restore();
+        self.chars
+    }
+
+    fn restore(&mut self) {
+        if mem::take(&mut self.swapped) {
+            self.chars.swap(self.idx - 1, self.idx);
+        }
+    }
+}
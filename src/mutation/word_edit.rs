@@ -0,0 +1,75 @@
+use crate::mutation::Edit;
+use itertools::Itertools;
+use std::rc::Rc;
+use types::MutationOperator;
+
+// edits that move whole words around, the words of the original move along with them so every
+// mutated word is still checked against the word it came from
+pub(super) fn word_edits(
+    chars: &[u8],
+    aligned: &str,
+    operators: impl IntoIterator<Item = MutationOperator>,
+) -> Vec<(Edit, Box<[u8]>, Rc<str>)> {
+    let words = chars.split(|it| *it == b'$').collect_vec();
+    let originals = aligned.split('$').collect_vec();
+    let starts = words
+        .iter()
+        .scan(0, |start, word| {
+            let word_start = *start;
+            *start += word.len() + 1;
+            Some(word_start)
+        })
+        .collect_vec();
+    let text = |word: &[u8]| String::from_utf8(word.to_vec()).unwrap();
+
+    let mut edits = vec![];
+    let mut push = |edit: Edit, order: Vec<usize>| {
+        let mutation = order.iter().map(|idx| words[*idx]).collect_vec().join(&b'$');
+        let aligned = order.iter().map(|idx| originals[*idx]).join("$");
+        edits.push((edit, mutation.into_boxed_slice(), Rc::from(aligned)));
+    };
+    let edit = |operator, position, word| Edit {
+        operator,
+        position,
+        old: None,
+        new: None,
+        word: Some(word),
+    };
+
+    for operator in operators {
+        match operator {
+            // spell names don't get to disappear entirely
+            MutationOperator::DropWord if words.len() > 1 => {
+                for idx in 0..words.len() {
+                    let order = (0..words.len()).filter(|it| *it != idx).collect_vec();
+                    let dropped = match idx + 1 == words.len() {
+                        true => edit(operator, starts[idx] - 1, format!(" {}", text(words[idx]))),
+                        false => edit(operator, starts[idx], format!("{} ", text(words[idx]))),
+                    };
+                    push(dropped, order);
+                }
+            }
+            MutationOperator::SwapWords => {
+                for idx in 1..words.len() {
+                    if words[idx - 1] == words[idx] {
+                        continue;
+                    }
+                    let mut order = (0..words.len()).collect_vec();
+                    order.swap(idx - 1, idx);
+                    let swapped = format!("{} {}", text(words[idx - 1]), text(words[idx]));
+                    push(edit(operator, starts[idx - 1], swapped), order);
+                }
+            }
+            MutationOperator::DuplicateWord => {
+                for idx in 0..words.len() {
+                    let mut order = (0..words.len()).collect_vec();
+                    order.insert(idx, idx);
+                    let position = starts[idx] + words[idx].len();
+                    push(edit(operator, position, format!(" {}", text(words[idx]))), order);
+                }
+            }
+            _ => {}
+        }
+    }
+    edits
+}
@@ -0,0 +1,104 @@
+use crate::spellchecking::trie::WordTrie;
+use crate::spellchecking::{CheckResult, SpellChecker};
+use std::cmp::min;
+use std::collections::HashMap;
+use types::{CompositeAccept, CompositeResult};
+
+pub struct CompositeSpellChecker {
+    accept: CompositeAccept,
+    result: CompositeResult,
+    members: Vec<CompositeMember>,
+    trie: Option<WordTrie>,
+}
+
+pub struct CompositeMember {
+    pub checker: Box<dyn SpellChecker>,
+    pub reject: Vec<u8>,
+    pub remap: HashMap<u8, u8>,
+}
+
+impl CompositeSpellChecker {
+    pub fn new(
+        accept: CompositeAccept,
+        result: CompositeResult,
+        members: Vec<CompositeMember>,
+    ) -> Self {
+        // any word accepted by the composite is accepted by at least one of its members
+        let trie = members.iter().try_fold(WordTrie::new(), |mut acc, member| {
+            acc.extend(member.checker.words()?);
+            Some(acc)
+        });
+        Self {
+            accept,
+            result,
+            members,
+            trie,
+        }
+    }
+
+    fn combine(&self, check: impl Fn(&dyn SpellChecker) -> CheckResult) -> CheckResult {
+        let mut combined: Option<CheckResult> = None;
+        for member in &self.members {
+            let result = member.check(&check);
+            if result.is_fail() {
+                match self.accept {
+                    CompositeAccept::All => return CheckResult::FAIL,
+                    CompositeAccept::Any => continue,
+                }
+            }
+            combined = Some(match (combined, self.result) {
+                (None, _) => result,
+                (Some(combined), CompositeResult::Best) => min(combined, result),
+                (Some(combined), CompositeResult::Worst) => combined.worst(result),
+                (Some(combined), CompositeResult::First) => combined,
+            });
+        }
+        combined.unwrap_or(CheckResult::FAIL)
+    }
+}
+
+impl CompositeMember {
+    fn check(&self, check: impl Fn(&dyn SpellChecker) -> CheckResult) -> CheckResult {
+        let result = check(self.checker.as_ref());
+        if result.is_fail() || self.reject.contains(&result.value()) {
+            return CheckResult::FAIL;
+        }
+        self.remap
+            .get(&result.value())
+            .map_or(result, |code| CheckResult::new(*code))
+    }
+}
+
+impl SpellChecker for CompositeSpellChecker {
+    fn name(&self) -> &'static str {
+        "Composite"
+    }
+
+    fn check(&self, original: &str, word: &str) -> CheckResult {
+        self.combine(|checker| checker.check(original, word))
+    }
+
+    fn words(&self) -> Option<&WordTrie> {
+        self.trie.as_ref()
+    }
+
+    fn check_split(&self, original: &str, string: &str) -> CheckResult {
+        self.combine(|checker| checker.check_split(original, string))
+    }
+
+    fn check_mutation(&self, name: &str, processed: &str, mutation: &str) -> CheckResult {
+        self.combine(|checker| checker.check_mutation(name, processed, mutation))
+    }
+
+    // only members that accept the mutation get a say
+    fn forms_phrase(&self, name: &str, processed: &str, mutation: &str) -> bool {
+        self.members.iter().any(|member| {
+            let accepted = member.check(|checker| checker.check_mutation(name, processed, mutation));
+            member.checker.forms_phrase(name, processed, mutation) && !accepted.is_fail()
+        })
+    }
+
+    fn is_proper_noun(&self, original: &str, word: &str) -> bool {
+        self.members.iter().any(|member| member.checker.is_proper_noun(original, word))
+    }
+}
@@ -1,8 +1,7 @@
+use crate::error::{parse_dictionary, read_file, Result};
+use crate::spellchecking::trie::WordTrie;
 use crate::spellchecking::{CheckResult, SpellChecker};
 use std::collections::HashMap;
-use std::fs;
-use std::iter::once;
-use std::sync::LazyLock;
 use itertools::Itertools;
 
 peg::parser!(
@@ -21,7 +20,7 @@ peg::parser!(
         }
 
         rule inflection_list() -> Vec<&'input str> = new_line() "    " infs:word() ** (_ "," _) {
-            infs.into_iter().filter_map(|x| x).collect_vec()
+            infs.into_iter().flatten().collect_vec()
         }
 
         rule head_word() -> (Option<&'input str>, Vec<&'input str>) =
@@ -35,20 +34,17 @@ peg::parser!(
     }
 );
 
-static FRQ_STRING: LazyLock<String> = LazyLock::new(|| {
-    fs::read_to_string("./dicts/12dicts-6.0.2/Lemmatized/2+2+3frq.txt")
-        .expect("failed to read dictionary")
-});
-
 pub struct FreqSpellChecker {
-    words: HashMap<&'static str, usize, rapidhash::fast::RandomState>,
+    words: HashMap<String, usize, rapidhash::fast::RandomState>,
     relations: Vec<(u8, Vec<usize>)>,
+    max_tier: u8,
+    trie: WordTrie,
 }
 
 impl FreqSpellChecker {
-    pub fn new() -> Self {
-        let parsed = freq_parser::dict(&FRQ_STRING)
-            .expect("couldn't parse dictionary");
+    pub fn new(path: &str, max_tier: Option<u8>) -> Result<Self> {
+        let dictionary = read_file(path)?;
+        let parsed = parse_dictionary(path, &dictionary, freq_parser::dict)?;
 
         let mut relations = vec![];
         let mut words = HashMap::with_hasher(rapidhash::fast::RandomState::new());
@@ -61,7 +57,7 @@ impl FreqSpellChecker {
                 }
                 let mut mesh = vec![];
                 for word in inflections {
-                    let idx = *words.entry(word).or_insert_with(|| {
+                    let idx = *words.entry(word.to_string()).or_insert_with(|| {
                         let next_idx = relations.len();
                         relations.push((freq_idx, vec![]));
                         next_idx
@@ -85,7 +81,20 @@ impl FreqSpellChecker {
             }
         }
 
-        Self { words, relations }
+        // tiers are numbered from 1, starting with the most frequent words
+        let max_tier = max_tier.unwrap_or(u8::MAX).saturating_sub(1);
+        let trie = WordTrie::from_words(
+            words
+                .iter()
+                .filter(|(_, idx)| relations[**idx].0 <= max_tier)
+                .map(|(word, _)| word.as_str()),
+        );
+        Ok(Self {
+            words,
+            relations,
+            max_tier,
+            trie,
+        })
     }
 }
 
@@ -95,11 +104,13 @@ impl SpellChecker for FreqSpellChecker {
     }
 
     fn check(&self, original: &str, word: &str) -> CheckResult {
-        char::is_ascii_alphabetic(&'b');
         if original == word {
             return CheckResult::SUCCESS;
         }
         if let Some(word_idx) = self.words.get(word) {
+            if self.relations[*word_idx].0 > self.max_tier {
+                return CheckResult::FAIL;
+            }
             let word_freq_code = self.relations[*word_idx].0.saturating_sub(16);
             if let Some(original_idx) = self.words.get(original) {
                 let relations = &self.relations[*word_idx].1;
@@ -111,4 +122,8 @@ impl SpellChecker for FreqSpellChecker {
         }
         CheckResult::FAIL
     }
+
+    fn words(&self) -> Option<&WordTrie> {
+        Some(&self.trie)
+    }
 }
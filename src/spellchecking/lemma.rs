@@ -1,13 +1,8 @@
+use crate::error::{parse_dictionary, read_file, Result};
+use crate::spellchecking::trie::WordTrie;
 use crate::spellchecking::{CheckResult, SpellChecker};
 use std::collections::HashMap;
-use std::fs;
 use std::iter::once;
-use std::sync::LazyLock;
-
-static LEMMA_STRING: LazyLock<String> = LazyLock::new(|| {
-    fs::read_to_string("./dicts/12dicts-6.0.2/Lemmatized/2+2+3lem.txt")
-        .expect("failed to read dictionary")
-});
 
 type ParsedWord<'a> = (&'a str, Vec<&'a str>);
 
@@ -34,13 +29,15 @@ peg::parser! {
 }
 
 pub struct LemmaSpellChecker {
-    words: HashMap<&'static str, usize, rapidhash::fast::RandomState>,
+    words: HashMap<String, usize, rapidhash::fast::RandomState>,
     relations: Vec<Vec<usize>>,
+    trie: WordTrie,
 }
 
 impl LemmaSpellChecker {
-    pub fn new() -> Self {
-        let parsed = lemma_parser::dict(LEMMA_STRING.as_str()).expect("couldn't parse dictionary");
+    pub fn new(path: &str) -> Result<Self> {
+        let dictionary = read_file(path)?;
+        let parsed = parse_dictionary(path, &dictionary, lemma_parser::dict)?;
 
         let mut relations = vec![];
         let mut words = HashMap::with_hasher(rapidhash::fast::RandomState::new());
@@ -48,7 +45,7 @@ impl LemmaSpellChecker {
         for (head, inflections) in parsed {
             let mut mesh = vec![];
             for parsed in inflections.iter().chain(once(&head)) {
-                let idx = *words.entry(parsed.0).or_insert_with(|| {
+                let idx = *words.entry(parsed.0.to_string()).or_insert_with(|| {
                     let next_idx = relations.len();
                     relations.push(vec![]);
                     next_idx
@@ -60,7 +57,8 @@ impl LemmaSpellChecker {
             }
         }
 
-        Self { relations, words }
+        let trie = WordTrie::from_words(words.keys().map(String::as_str));
+        Ok(Self { relations, words, trie })
     }
 }
 
@@ -84,4 +82,8 @@ impl SpellChecker for LemmaSpellChecker {
         }
         CheckResult::FAIL
     }
+
+    fn words(&self) -> Option<&WordTrie> {
+        Some(&self.trie)
+    }
 }
@@ -1,17 +1,94 @@
-use itertools::{EitherOrBoth, Itertools};
+use itertools::Itertools;
 use serde::{Deserialize, Serialize};
-use std::cmp::{max, min};
+use std::cmp::max;
 use std::fmt::Display;
+use crate::error::Result;
+use crate::spellchecking::composite::{CompositeMember, CompositeSpellChecker};
+use crate::spellchecking::freq::FreqSpellChecker;
+use crate::spellchecking::lemma::LemmaSpellChecker;
+use crate::spellchecking::old::OldSpellChecker;
+use crate::spellchecking::phrase::PhraseSpellChecker;
+use crate::spellchecking::pos::PosSpellChecker;
+use crate::spellchecking::proper::ProperNounSpellChecker;
+use crate::spellchecking::variant::VariantSpellChecker;
+use crate::spellchecking::trie::WordTrie;
+use types::SpellCheckerConfig;
 
+pub mod composite;
 pub mod freq;
 pub mod lemma;
 pub mod old;
+pub mod phrase;
+pub mod pos;
+pub mod proper;
+pub mod trie;
+pub mod variant;
+
+pub fn build_spell_checker(config: &SpellCheckerConfig) -> Result<Box<dyn SpellChecker>> {
+    Ok(match config {
+        SpellCheckerConfig::Lemma { dictionary } => Box::new(LemmaSpellChecker::new(dictionary)?),
+        SpellCheckerConfig::Frequency {
+            dictionary,
+            max_tier,
+        } => Box::new(FreqSpellChecker::new(dictionary, *max_tier)?),
+        SpellCheckerConfig::Legacy {
+            affixes,
+            dictionary,
+        } => Box::new(OldSpellChecker::new(affixes, dictionary)?),
+        SpellCheckerConfig::PartOfSpeech { dictionary } => {
+            Box::new(PosSpellChecker::new(dictionary)?)
+        }
+        SpellCheckerConfig::Phrase {
+            dictionary,
+            checker,
+        } => Box::new(PhraseSpellChecker::new(dictionary, build_spell_checker(checker)?)?),
+        SpellCheckerConfig::ProperNoun {
+            dictionary,
+            signatures,
+            checker,
+        } => Box::new(ProperNounSpellChecker::new(
+            dictionary,
+            signatures,
+            build_spell_checker(checker)?,
+        )?),
+        SpellCheckerConfig::Variant {
+            dictionary,
+            keep_variants,
+            checker,
+        } => Box::new(VariantSpellChecker::new(
+            dictionary,
+            *keep_variants,
+            build_spell_checker(checker)?,
+        )?),
+        SpellCheckerConfig::Composite {
+            accept,
+            result,
+            checkers,
+        } => {
+            let members = checkers
+                .iter()
+                .map(|member| {
+                    Ok(CompositeMember {
+                        checker: build_spell_checker(&member.checker)?,
+                        reject: member.reject.clone(),
+                        remap: member.remap.clone(),
+                    })
+                })
+                .collect::<Result<_>>()?;
+            Box::new(CompositeSpellChecker::new(*accept, *result, members))
+        }
+    })
+}
 
 pub trait SpellChecker: Send + Sync {
     fn name(&self) -> &'static str;
 
     fn check(&self, original: &str, word: &str) -> CheckResult;
 
+    fn words(&self) -> Option<&WordTrie> {
+        None
+    }
+
     fn check_split(&self, original: &str, string: &str) -> CheckResult {
         let mut result = CheckResult::SUCCESS;
         for word in string.split(' ') {
@@ -19,6 +96,28 @@ pub trait SpellChecker: Send + Sync {
         }
         result
     }
+
+    // the strings separate the words of the spell name with '$', processed lines the name's words
+    // up with the mutation's after word level edits moved them around
+    fn check_mutation(&self, _name: &str, processed: &str, mutation: &str) -> CheckResult {
+        processed
+            .split('$')
+            .zip_eq(mutation.split('$'))
+            .fold(CheckResult::SUCCESS, |b, (original, mutation)| {
+                self.check_split(original, mutation).worst(b)
+            })
+    }
+
+    // phrases are flagged next to the check result, no code could sort them ahead of the words
+    // that need no code at all
+    fn forms_phrase(&self, _name: &str, _processed: &str, _mutation: &str) -> bool {
+        false
+    }
+
+    // whether the word is only known as a name, which reads better capitalized
+    fn is_proper_noun(&self, _original: &str, _word: &str) -> bool {
+        false
+    }
 }
 
 #[derive(Copy, Clone, Hash, Ord, PartialOrd, Eq, PartialEq, Debug, Serialize, Deserialize)]
@@ -31,7 +130,7 @@ impl CheckResult {
     pub const FAIL: Self = Self { value: u8::MAX };
     pub const SUCCESS: Self = Self { value: 0 };
 
-    pub fn new(code: u8) -> Self {
+    pub const fn new(code: u8) -> Self {
         Self { value: code }
     }
 
@@ -1,5 +1,6 @@
+use crate::error::{read_file, Error, Result};
 use crate::spellchecking::{CheckResult, SpellChecker};
-use std::fs;
+use std::path::PathBuf;
 use zspell::Dictionary;
 
 pub struct OldSpellChecker {
@@ -7,19 +8,20 @@ pub struct OldSpellChecker {
 }
 
 impl OldSpellChecker {
-    pub(crate) fn new() -> OldSpellChecker {
-        let aff_content =
-            fs::read_to_string("./dicts/lang_en_US.aff").expect("failed to load lang config");
-        let dict_content =
-            fs::read_to_string("./dicts/lang_en_US_DICT.dic").expect("failed to load dictionary");
+    pub(crate) fn new(affixes: &str, dictionary: &str) -> Result<OldSpellChecker> {
+        let aff_content = read_file(affixes)?;
+        let dict_content = read_file(dictionary)?;
 
         let dictionary = zspell::builder()
             .dict_str(&dict_content)
             .config_str(&aff_content)
             .build()
-            .expect("failed to init language");
+            .map_err(|source| Error::Language {
+                path: PathBuf::from(affixes),
+                source,
+            })?;
 
-        Self { dictionary }
+        Ok(Self { dictionary })
     }
 }
 
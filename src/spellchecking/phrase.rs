@@ -0,0 +1,90 @@
+use crate::error::{read_file, Result};
+use crate::mutation::normalize_name;
+use crate::spellchecking::trie::WordTrie;
+use crate::spellchecking::{CheckResult, SpellChecker};
+use itertools::Itertools;
+use std::cmp::max;
+use std::collections::HashSet;
+
+pub struct PhraseSpellChecker {
+    checker: Box<dyn SpellChecker>,
+    phrases: HashSet<String, rapidhash::fast::RandomState>,
+    max_words: usize,
+}
+
+impl PhraseSpellChecker {
+    pub fn new(path: &str, checker: Box<dyn SpellChecker>) -> Result<Self> {
+        let dictionary = read_file(path)?;
+
+        let mut phrases = HashSet::with_hasher(rapidhash::fast::RandomState::new());
+        let mut max_words = 0;
+        for line in dictionary.lines() {
+            // phrasal verbs end with a ';', they match most "<verb> on" and "<verb> of" mutations
+            if line.ends_with(';') {
+                continue;
+            }
+            // phrases with letters outside ascii can't come out of a mutation
+            let Ok(phrase) = normalize_name(line) else {
+                continue;
+            };
+            let words = phrase.split(' ').count();
+            if words > 1 {
+                max_words = max(max_words, words);
+                phrases.insert(phrase);
+            }
+        }
+
+        Ok(Self {
+            checker,
+            phrases,
+            max_words,
+        })
+    }
+
+    fn contains_phrase(&self, words: &[&str], ignored: &HashSet<String>) -> bool {
+        (2..=self.max_words).any(|len| {
+            words.windows(len).any(|span| {
+                let span = span.join(" ");
+                !ignored.contains(&span) && self.phrases.contains(&span)
+            })
+        })
+    }
+}
+
+impl SpellChecker for PhraseSpellChecker {
+    fn name(&self) -> &'static str {
+        "Phrase"
+    }
+
+    fn check(&self, original: &str, word: &str) -> CheckResult {
+        self.checker.check(original, word)
+    }
+
+    fn words(&self) -> Option<&WordTrie> {
+        self.checker.words()
+    }
+
+    fn check_split(&self, original: &str, string: &str) -> CheckResult {
+        self.checker.check_split(original, string)
+    }
+
+    fn check_mutation(&self, name: &str, processed: &str, mutation: &str) -> CheckResult {
+        self.checker.check_mutation(name, processed, mutation)
+    }
+
+    fn forms_phrase(&self, name: &str, processed: &str, mutation: &str) -> bool {
+        let words = mutation.split(['$', ' ']).collect_vec();
+        // phrases the spell name already had aren't the mutation's doing
+        let original_words = processed.split('$').collect_vec();
+        let mut original_phrases = HashSet::new();
+        for len in 2..=self.max_words {
+            original_phrases.extend(original_words.windows(len).map(|span| span.join(" ")));
+        }
+        self.contains_phrase(&words, &original_phrases)
+            || self.checker.forms_phrase(name, processed, mutation)
+    }
+
+    fn is_proper_noun(&self, original: &str, word: &str) -> bool {
+        self.checker.is_proper_noun(original, word)
+    }
+}
@@ -0,0 +1,98 @@
+use crate::error::{parse_dictionary, read_file, Result};
+use crate::spellchecking::trie::WordTrie;
+use crate::spellchecking::{CheckResult, SpellChecker};
+use std::collections::HashMap;
+
+type ParsedEntry<'a> = (Option<&'a str>, u8, Vec<Option<&'a str>>);
+
+peg::parser! {
+    grammar pos_parser() for str {
+        rule _() = " "*
+
+        rule new_line() = "\r"? "\n"
+
+        rule letters() -> &'input str = $(['a'..='z' | 'A'..='Z' | '\'']+)
+
+        rule note() = "{" [^ '}']* "}"
+
+        // a leading '-' marks rare forms and '@' archaic ones, neither is worth a mutation
+        rule form() -> Option<&'input str> =
+            "-" "@"? letters() { None }
+            / "-" { None }
+            / "@" letters() { None }
+            / ['~' | '!']? w:letters() { Some(w) }
+
+        rule token() -> Option<&'input str> =
+            note() { None } / ['(' | ')' | '/' | '|'] { None } / form()
+
+        rule part_of_speech() -> u8 =
+            "N" { NOUN } / "V" { VERB } / "A" { ADJECTIVE } / "C" { CONJUNCTION }
+            / "I" { INTERJECTION } / "P" { PRONOUN } / "S" { CONTRACTION }
+
+        rule entry() -> ParsedEntry<'input> =
+            "+"? head:form() _ pos:part_of_speech() _ note()? ":" _ forms:(token() ** _) _
+        { (head, pos, forms) }
+
+        pub rule dict() -> Vec<ParsedEntry<'input>> = e:entry() ** new_line() new_line()? { e }
+    }
+}
+
+const NOUN: u8 = 1 << 0;
+const VERB: u8 = 1 << 1;
+const ADJECTIVE: u8 = 1 << 2;
+const CONJUNCTION: u8 = 1 << 3;
+const INTERJECTION: u8 = 1 << 4;
+const PRONOUN: u8 = 1 << 5;
+const CONTRACTION: u8 = 1 << 6;
+
+pub struct PosSpellChecker {
+    words: HashMap<String, u8, rapidhash::fast::RandomState>,
+    trie: WordTrie,
+}
+
+impl PosSpellChecker {
+    pub fn new(path: &str) -> Result<Self> {
+        let dictionary = read_file(path)?;
+        let parsed = parse_dictionary(path, &dictionary, pos_parser::dict)?;
+
+        let mut words = HashMap::with_hasher(rapidhash::fast::RandomState::new());
+        for (head, pos, forms) in parsed {
+            // rare usages of a word don't count towards its parts of speech
+            let Some(head) = head else {
+                continue;
+            };
+            for word in forms.into_iter().flatten().chain([head]) {
+                *words.entry(word.to_string()).or_insert(0) |= pos;
+            }
+        }
+
+        let trie = WordTrie::from_words(words.keys().map(String::as_str));
+        Ok(Self { words, trie })
+    }
+}
+
+impl SpellChecker for PosSpellChecker {
+    fn name(&self) -> &'static str {
+        "PartOfSpeech"
+    }
+
+    // 1 when the word can't be used as the same part of speech as the original, 2 when the
+    // original's part of speech is unknown
+    fn check(&self, original: &str, word: &str) -> CheckResult {
+        if original == word {
+            return CheckResult::SUCCESS;
+        }
+        let Some(pos) = self.words.get(word) else {
+            return CheckResult::FAIL;
+        };
+        match self.words.get(original) {
+            Some(original_pos) if original_pos & pos != 0 => CheckResult::SUCCESS,
+            Some(_) => CheckResult::new(1),
+            None => CheckResult::new(2),
+        }
+    }
+
+    fn words(&self) -> Option<&WordTrie> {
+        Some(&self.trie)
+    }
+}
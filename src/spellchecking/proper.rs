@@ -0,0 +1,119 @@
+use crate::error::{read_file, Result};
+use crate::spellchecking::trie::WordTrie;
+use crate::spellchecking::{CheckResult, SpellChecker};
+use itertools::Itertools;
+use std::collections::HashMap;
+
+pub const SIGNATURE: CheckResult = CheckResult::new(140);
+pub const PROPER_NOUN: CheckResult = CheckResult::new(150);
+
+pub struct ProperNounSpellChecker {
+    checker: Box<dyn SpellChecker>,
+    // whether each word is capitalized in the word lists
+    words: HashMap<String, bool, rapidhash::fast::RandomState>,
+    trie: Option<WordTrie>,
+}
+
+impl ProperNounSpellChecker {
+    pub fn new(
+        dictionary: &str,
+        signatures: &[String],
+        checker: Box<dyn SpellChecker>,
+    ) -> Result<Self> {
+        let mut words = HashMap::with_hasher(rapidhash::fast::RandomState::new());
+        // the desk dictionary is mostly lowercase words the other checkers already know
+        for line in read_file(dictionary)?.lines() {
+            if line.starts_with(|it: char| it.is_ascii_uppercase()) {
+                insert_word(&mut words, line);
+            }
+        }
+        for signature in signatures {
+            for line in read_file(signature)?.lines() {
+                insert_word(&mut words, line);
+            }
+        }
+
+        let trie = checker.words().map(|inner| {
+            let mut trie = WordTrie::from_words(words.keys().map(String::as_str));
+            trie.extend(inner);
+            trie
+        });
+        Ok(Self {
+            checker,
+            words,
+            trie,
+        })
+    }
+
+    fn check_proper(&self, word: &str) -> CheckResult {
+        match self.words.get(word) {
+            Some(true) => PROPER_NOUN,
+            Some(false) => SIGNATURE,
+            None => CheckResult::FAIL,
+        }
+    }
+}
+
+fn insert_word(words: &mut HashMap<String, bool, rapidhash::fast::RandomState>, word: &str) {
+    if word.len() < 2 || !word.bytes().all(|it| it.is_ascii_alphabetic()) {
+        return;
+    }
+    // abbreviations like "ATM" don't read as words
+    if !word.bytes().any(|it| it.is_ascii_lowercase()) {
+        return;
+    }
+    let capitalized = word.starts_with(|it: char| it.is_ascii_uppercase());
+    *words.entry(word.to_ascii_lowercase()).or_default() |= capitalized;
+}
+
+impl SpellChecker for ProperNounSpellChecker {
+    fn name(&self) -> &'static str {
+        "ProperNoun"
+    }
+
+    fn check(&self, original: &str, word: &str) -> CheckResult {
+        let result = self.checker.check(original, word);
+        if !result.is_fail() {
+            return result;
+        }
+        self.check_proper(word)
+    }
+
+    fn words(&self) -> Option<&WordTrie> {
+        self.trie.as_ref()
+    }
+
+    // the wrapped checker has the first say over the whole mutation, proper nouns only stand in
+    // for the words it doesn't know
+    fn check_mutation(&self, name: &str, processed: &str, mutation: &str) -> CheckResult {
+        let result = self.checker.check_mutation(name, processed, mutation);
+        if !result.is_fail() {
+            return result;
+        }
+        let mut result = CheckResult::SUCCESS;
+        let mut proper = false;
+        for (original, split) in processed.split('$').zip_eq(mutation.split('$')) {
+            for word in split.split(' ') {
+                let mut checked = self.checker.check(original, word);
+                if checked.is_fail() {
+                    proper = true;
+                    checked = self.check_proper(word);
+                }
+                result = result.worst(checked);
+            }
+        }
+        match proper {
+            true => result,
+            false => CheckResult::FAIL,
+        }
+    }
+
+    fn forms_phrase(&self, name: &str, processed: &str, mutation: &str) -> bool {
+        self.checker.forms_phrase(name, processed, mutation)
+    }
+
+    fn is_proper_noun(&self, original: &str, word: &str) -> bool {
+        self.checker.is_proper_noun(original, word)
+            || self.checker.check(original, word).is_fail() && self.words.get(word) == Some(&true)
+    }
+}
@@ -0,0 +1,234 @@
+use std::cmp::min;
+
+const NO_CHILD: u32 = 0;
+
+pub struct WordTrie {
+    nodes: Vec<TrieNode>,
+}
+
+#[derive(Clone, Default)]
+struct TrieNode {
+    children: [u32; 26],
+    terminal: bool,
+}
+
+impl Default for WordTrie {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WordTrie {
+    pub fn new() -> Self {
+        Self {
+            nodes: vec![TrieNode::default()],
+        }
+    }
+
+    pub fn from_words<'a>(words: impl IntoIterator<Item = &'a str>) -> Self {
+        let mut trie = Self::new();
+        for word in words {
+            trie.insert(word);
+        }
+        trie
+    }
+
+    // mutations only ever contain lowercase letters, so nothing else can match
+    pub fn insert(&mut self, word: &str) {
+        if word.is_empty() || !word.bytes().all(|it| it.is_ascii_lowercase()) {
+            return;
+        }
+        let mut node = 0;
+        for letter in word.bytes() {
+            let slot = (letter - b'a') as usize;
+            if self.nodes[node].children[slot] == NO_CHILD {
+                self.nodes[node].children[slot] = self.nodes.len() as u32;
+                self.nodes.push(TrieNode::default());
+            }
+            node = self.nodes[node].children[slot] as usize;
+        }
+        self.nodes[node].terminal = true;
+    }
+
+    pub fn extend(&mut self, other: &WordTrie) {
+        self.merge(0, other, 0);
+    }
+
+    fn merge(&mut self, node: usize, other: &WordTrie, other_node: usize) {
+        if other.nodes[other_node].terminal {
+            self.nodes[node].terminal = true;
+        }
+        for slot in 0..26 {
+            let other_child = other.nodes[other_node].children[slot];
+            if other_child == NO_CHILD {
+                continue;
+            }
+            if self.nodes[node].children[slot] == NO_CHILD {
+                self.nodes[node].children[slot] = self.nodes.len() as u32;
+                self.nodes.push(TrieNode::default());
+            }
+            let child = self.nodes[node].children[slot] as usize;
+            self.merge(child, other, other_child as usize);
+        }
+    }
+
+    pub fn contains(&self, word: &[u8]) -> bool {
+        self.find(word).is_some_and(|node| self.nodes[node].terminal)
+    }
+
+    pub fn contains_prefix(&self, prefix: &[u8]) -> bool {
+        self.find(prefix).is_some()
+    }
+
+    fn find(&self, prefix: &[u8]) -> Option<usize> {
+        let mut node = 0;
+        for letter in prefix {
+            if !letter.is_ascii_lowercase() {
+                return None;
+            }
+            node = self.nodes[node].children[(letter - b'a') as usize] as usize;
+            if node == NO_CHILD as usize {
+                return None;
+            }
+        }
+        Some(node)
+    }
+
+    fn walk(
+        &self,
+        node: usize,
+        rows: &mut [u8],
+        tries: &[&WordTrie],
+        word: &[u8],
+        budget: u8,
+    ) -> bool {
+        let width = word.len() + 1;
+        let (row, rows) = rows.split_at_mut(width);
+        if self.nodes[node].terminal {
+            if row[word.len()] <= budget {
+                return true;
+            }
+            // end the word here, insert a space and continue with the rest
+            for split in 1..word.len() {
+                if row[split] < budget && fits(tries, &word[split..], budget - row[split] - 1) {
+                    return true;
+                }
+            }
+        }
+        if rows.len() < width {
+            return false;
+        }
+
+        for (slot, child) in self.nodes[node].children.iter().enumerate() {
+            if *child == NO_CHILD {
+                continue;
+            }
+            let letter = b'a' + slot as u8;
+            let next_row = &mut rows[..width];
+            next_row[0] = row[0] + 1;
+            let mut best = next_row[0];
+            for idx in 1..width {
+                next_row[idx] = min(
+                    min(row[idx], next_row[idx - 1]) + 1,
+                    row[idx - 1] + (word[idx - 1] != letter) as u8,
+                );
+                best = min(best, next_row[idx]);
+            }
+            if best <= budget && self.walk(*child as usize, rows, tries, word, budget) {
+                return true;
+            }
+        }
+        false
+    }
+}
+
+/// The fewest insertions, removals, changes and added spaces needed to turn `word` into a space
+/// separated sequence of words from `tries`, if it can be done in at most `max` edits.
+pub fn min_edits(tries: &[&WordTrie], word: &[u8], max: usize) -> Option<usize> {
+    // edit distances are tracked in bytes, give up on words too long for that
+    if word.len() >= u8::MAX as usize / 2 {
+        return Some(0);
+    }
+    let max = min(max, u8::MAX as usize / 2) as u8;
+    (0..=max)
+        .find(|budget| fits(tries, word, *budget))
+        .map(usize::from)
+}
+
+fn fits(tries: &[&WordTrie], word: &[u8], budget: u8) -> bool {
+    if tries.iter().any(|trie| trie.contains(word)) {
+        return true;
+    }
+    if budget == 0 {
+        return false;
+    }
+    // one row per trie level, words in the trie can't be longer than the word plus the budget
+    let width = word.len() + 1;
+    let mut rows = vec![0; width * (width + budget as usize + 1)];
+    for (idx, cell) in rows[..width].iter_mut().enumerate() {
+        *cell = idx as u8;
+    }
+    tries.iter().any(|trie| trie.walk(0, &mut rows, tries, word, budget))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dictionary() -> WordTrie {
+        WordTrie::from_words(["fire", "bolt", "fireball", "cure"])
+    }
+
+    #[test]
+    fn words_in_the_trie_need_no_edits() {
+        let trie = dictionary();
+        assert_eq!(min_edits(&[&trie], b"fire", 0), Some(0));
+        assert_eq!(min_edits(&[&trie], b"fireball", 2), Some(0));
+    }
+
+    #[test]
+    fn counts_changes_insertions_and_removals() {
+        let trie = dictionary();
+        assert_eq!(min_edits(&[&trie], b"fird", 3), Some(1));
+        assert_eq!(min_edits(&[&trie], b"fre", 3), Some(1));
+        assert_eq!(min_edits(&[&trie], b"firre", 3), Some(1));
+        assert_eq!(min_edits(&[&trie], b"bo", 3), Some(2));
+    }
+
+    #[test]
+    fn gives_up_past_the_maximum() {
+        let trie = dictionary();
+        assert_eq!(min_edits(&[&trie], b"bo", 1), None);
+        assert_eq!(min_edits(&[&trie], b"xyzzy", 2), None);
+        assert_eq!(min_edits(&[&trie], b"fird", 0), None);
+    }
+
+    #[test]
+    fn splitting_a_word_costs_an_edit() {
+        let trie = dictionary();
+        assert_eq!(min_edits(&[&trie], b"firebolt", 0), None);
+        assert_eq!(min_edits(&[&trie], b"firebolt", 3), Some(1));
+        assert_eq!(min_edits(&[&trie], b"firbolt", 3), Some(2));
+        assert_eq!(min_edits(&[&trie], b"curefirebolt", 3), Some(2));
+    }
+
+    #[test]
+    fn splits_can_mix_words_from_every_trie() {
+        let trie = dictionary();
+        let originals = WordTrie::from_words(["magic"]);
+        assert_eq!(min_edits(&[&trie], b"magicbolt", 3), None);
+        assert_eq!(min_edits(&[&trie, &originals], b"magicbolt", 3), Some(1));
+        assert_eq!(min_edits(&[&trie, &originals], b"boltmagik", 3), Some(2));
+    }
+
+    #[test]
+    fn merged_tries_hold_the_words_of_both() {
+        let mut trie = dictionary();
+        trie.extend(&WordTrie::from_words(["bolts", "wish"]));
+        assert!(trie.contains(b"bolts"));
+        assert!(trie.contains(b"bolt"));
+        assert!(trie.contains(b"wish"));
+        assert!(!trie.contains(b"wis"));
+        assert!(trie.contains_prefix(b"wis"));
+    }
+}
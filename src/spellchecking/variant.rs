@@ -0,0 +1,246 @@
+use crate::error::{read_file, Result};
+use crate::spellchecking::trie::WordTrie;
+use crate::spellchecking::{CheckResult, SpellChecker};
+use itertools::Itertools;
+use std::collections::{HashMap, HashSet};
+
+pub const VARIANT: CheckResult = CheckResult::new(250);
+
+// variant.txt only lists the variants themselves, these rewrites find the spelling they're a
+// variant of
+const REWRITES: &[(&str, &str)] = &[
+    ("our", "or"),
+    ("re", "er"),
+    ("er", "re"),
+    ("or", "er"),
+    ("er", "or"),
+    ("is", "iz"),
+    ("iz", "is"),
+    ("ys", "yz"),
+    ("ae", "e"),
+    ("oe", "e"),
+    ("ou", "o"),
+    ("ey", "ay"),
+    ("ph", "f"),
+    ("ce", "se"),
+    ("gue", "g"),
+    ("mme", "m"),
+    ("ck", "c"),
+    ("que", "ck"),
+    ("y", "ie"),
+    ("ei", "ee"),
+];
+const VOWELS: &[u8] = b"aeiou";
+
+pub struct VariantSpellChecker {
+    checker: Box<dyn SpellChecker>,
+    variants: HashMap<String, HashSet<String>, rapidhash::fast::RandomState>,
+    keep_variants: bool,
+    trie: Option<WordTrie>,
+}
+
+impl VariantSpellChecker {
+    pub fn new(dictionary: &str, keep_variants: bool, checker: Box<dyn SpellChecker>) -> Result<Self> {
+        let mut variants: HashMap<_, HashSet<_>, _> =
+            HashMap::with_hasher(rapidhash::fast::RandomState::new());
+        for line in read_file(dictionary)?.lines() {
+            let Some((variant, _)) = line.split_once('\t') else {
+                continue;
+            };
+            let variant = variant.to_ascii_lowercase();
+            if !variant.bytes().all(|it| it.is_ascii_lowercase()) {
+                continue;
+            }
+            let spellings = spellings(&variant).into_iter().find_map(|group| {
+                let found = group
+                    .into_iter()
+                    .filter(|it| *it != variant && !checker.check("", it).is_fail())
+                    .collect_vec();
+                (!found.is_empty()).then_some(found)
+            });
+            for spelling in spellings.into_iter().flatten() {
+                variants.entry(variant.clone()).or_default().insert(spelling.clone());
+                variants.entry(spelling).or_default().insert(variant.clone());
+            }
+        }
+
+        let trie = checker.words().map(|inner| {
+            let mut trie = WordTrie::from_words(variants.keys().map(String::as_str));
+            trie.extend(inner);
+            trie
+        });
+        Ok(Self {
+            checker,
+            variants,
+            keep_variants,
+            trie,
+        })
+    }
+
+    fn is_variant(&self, original: &str, word: &str) -> bool {
+        self.variants
+            .get(original)
+            .is_some_and(|variants| variants.contains(word))
+    }
+}
+
+// spellings the variant could be a variant of, from the most to the least likely, only the first
+// group containing real words counts
+fn spellings(variant: &str) -> [Vec<String>; 4] {
+    let letters = variant.as_bytes();
+    let remove = |idx: usize| format!("{}{}", &variant[..idx], &variant[idx + 1..]);
+    let insert = |idx: usize, letter: u8| {
+        format!("{}{}{}", &variant[..idx], letter as char, &variant[idx..])
+    };
+
+    let rewrites = REWRITES
+        .iter()
+        .flat_map(|&(from, to)| {
+            variant.match_indices(from).map(move |(idx, _)| {
+                format!("{}{}{}", &variant[..idx], to, &variant[idx + from.len()..])
+            })
+        })
+        .collect();
+    let doubled = (0..letters.len())
+        .filter(|idx| !VOWELS.contains(&letters[*idx]))
+        .flat_map(|idx| {
+            let removed = (idx > 0 && letters[idx - 1] == letters[idx]).then(|| remove(idx));
+            removed.into_iter().chain([insert(idx, letters[idx])])
+        })
+        .collect();
+    let final_e = match variant.strip_suffix('e') {
+        Some(stripped) => vec![stripped.to_string()],
+        None => vec![format!("{variant}e")],
+    };
+    // a vowel more or less turns short words into entirely different ones
+    let mut vowels = vec![];
+    if letters.len() >= 5 {
+        for idx in 0..=letters.len() {
+            if idx < letters.len() && VOWELS.contains(&letters[idx]) {
+                vowels.push(remove(idx));
+            }
+            vowels.extend(VOWELS.iter().map(|vowel| insert(idx, *vowel)));
+        }
+    }
+    [rewrites, doubled, final_e, vowels]
+}
+
+impl SpellChecker for VariantSpellChecker {
+    fn name(&self) -> &'static str {
+        "Variant"
+    }
+
+    fn check(&self, original: &str, word: &str) -> CheckResult {
+        if self.is_variant(original, word) {
+            return VARIANT;
+        }
+        self.checker.check(original, word)
+    }
+
+    fn words(&self) -> Option<&WordTrie> {
+        self.trie.as_ref()
+    }
+
+    // variants of the original count as the original itself, so the mutation has to change
+    // something else to be more than a variant, moving the name's words around counts
+    fn check_mutation(&self, name: &str, processed: &str, mutation: &str) -> CheckResult {
+        let mut result = CheckResult::SUCCESS;
+        let mut changed = name != processed;
+        for (original, split) in processed.split('$').zip_eq(mutation.split('$')) {
+            if original == split || self.is_variant(original, split) {
+                continue;
+            }
+            changed = true;
+            result = result.worst(self.checker.check_split(original, split));
+        }
+        match changed {
+            true => result,
+            false if self.keep_variants => VARIANT,
+            false => CheckResult::FAIL,
+        }
+    }
+
+    fn forms_phrase(&self, name: &str, processed: &str, mutation: &str) -> bool {
+        self.checker.forms_phrase(name, processed, mutation)
+    }
+
+    fn is_proper_noun(&self, original: &str, word: &str) -> bool {
+        self.checker.is_proper_noun(original, word)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Words(&'static [&'static str]);
+
+    impl SpellChecker for Words {
+        fn name(&self) -> &'static str {
+            "Words"
+        }
+
+        fn check(&self, _original: &str, word: &str) -> CheckResult {
+            match self.0.contains(&word) {
+                true => CheckResult::SUCCESS,
+                false => CheckResult::FAIL,
+            }
+        }
+    }
+
+    fn checker(keep_variants: bool) -> VariantSpellChecker {
+        let mut variants: HashMap<_, HashSet<_>, _> =
+            HashMap::with_hasher(rapidhash::fast::RandomState::new());
+        variants.entry("colour".to_string()).or_default().insert("color".to_string());
+        variants.entry("color".to_string()).or_default().insert("colour".to_string());
+        VariantSpellChecker {
+            checker: Box::new(Words(&["color", "spray", "stray"])),
+            variants,
+            keep_variants,
+            trie: None,
+        }
+    }
+
+    #[test]
+    fn spellings_find_the_spelling_of_a_variant() {
+        let group = |variant: &str, spelling: &str| {
+            spellings(variant).iter().position(|group| group.iter().any(|it| it == spelling))
+        };
+        assert_eq!(group("colour", "color"), Some(0));
+        assert_eq!(group("metre", "meter"), Some(0));
+        assert_eq!(group("matt", "mat"), Some(1));
+    }
+
+    #[test]
+    fn only_variants_are_no_mutation() {
+        let checker = checker(false);
+        assert!(checker.check_mutation("colour$spray", "colour$spray", "color$spray").is_fail());
+        let result = checker.check_mutation("colour$spray", "colour$spray", "color$stray");
+        assert_eq!(result, CheckResult::SUCCESS);
+        let result = checker.check_mutation("colour$spray", "colour$spray", "colour$stray");
+        assert_eq!(result, CheckResult::SUCCESS);
+    }
+
+    #[test]
+    fn kept_variants_are_flagged() {
+        let checker = checker(true);
+        let result = checker.check_mutation("colour$spray", "colour$spray", "color$spray");
+        assert_eq!(result, VARIANT);
+        let result = checker.check_mutation("colour$spray", "colour$spray", "color$stray");
+        assert_eq!(result, CheckResult::SUCCESS);
+    }
+
+    #[test]
+    fn moving_words_is_a_mutation() {
+        for keep_variants in [false, true] {
+            let checker = checker(keep_variants);
+            let result = checker.check_mutation("colour$spray", "spray$colour", "spray$color");
+            assert_eq!(result, CheckResult::SUCCESS);
+            let result = checker.check_mutation("colour$spray", "spray", "spray");
+            assert_eq!(result, CheckResult::SUCCESS);
+            let duplicated = "colour$colour$spray";
+            let result = checker.check_mutation("colour$spray", duplicated, "colour$color$spray");
+            assert_eq!(result, CheckResult::SUCCESS);
+        }
+    }
+}